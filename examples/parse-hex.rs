@@ -0,0 +1,107 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! This example parses a service advertisement from the command line (without any BLE hardware)
+//! and prints the device type and the contained sensor values to stdout.
+//!
+//! Usage: `cargo run --example parse-hex -- <SERVICE_UUID> <HEX_PAYLOAD>`
+//!
+//! The service UUID can either be a full UUID or a 16-bit UUID, e.g.:
+//!
+//! ```text
+//! cargo run --example parse-hex -- fe95 712098...
+//! ```
+
+use std::env;
+use std::process::ExitCode;
+use uuid::Uuid;
+use xiaomi_ble::service::{ServiceAdvertisement, ServiceAdvertisementError};
+use xiaomi_ble::{parse_service_advertisement, parse_service_advertisement_u16};
+
+/// A service UUID given on the command line.
+enum ServiceUuid {
+    /// 16-bit UUID (e.g. `fe95` or `0xFE95`)
+    Short(u16),
+    /// Full UUID
+    Full(Uuid),
+}
+
+impl ServiceUuid {
+    /// Parse a full UUID or a 16-bit UUID (e.g. `fe95` or `0xFE95`).
+    fn parse(value: &str) -> Option<Self> {
+        let short_uuid = value.strip_prefix("0x").unwrap_or(value);
+        if short_uuid.len() == 4 {
+            return u16::from_str_radix(short_uuid, 16).ok().map(Self::Short);
+        }
+        Uuid::parse_str(value).ok().map(Self::Full)
+    }
+
+    /// Parse a service advertisement payload corresponding to this UUID.
+    fn parse_advertisement(
+        &self,
+        payload: &[u8],
+    ) -> Result<ServiceAdvertisement, ServiceAdvertisementError> {
+        match self {
+            Self::Short(uuid) => parse_service_advertisement_u16(*uuid, payload),
+            Self::Full(uuid) => parse_service_advertisement(uuid, payload),
+        }
+    }
+}
+
+/// Parse a hex string (ignoring whitespace, `:` and `-` separators) into bytes.
+fn parse_hex(value: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = value
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace() && *c != b':' && *c != b'-')
+        .collect();
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).ok()?;
+            u8::from_str_radix(pair, 16).ok()
+        })
+        .collect()
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let [_, uuid, payload] = args.as_slice() else {
+        eprintln!("Usage: parse-hex <SERVICE_UUID> <HEX_PAYLOAD>");
+        return ExitCode::FAILURE;
+    };
+    let Some(uuid) = ServiceUuid::parse(uuid) else {
+        eprintln!("Invalid service UUID: {uuid}");
+        return ExitCode::FAILURE;
+    };
+    let Some(payload) = parse_hex(payload) else {
+        eprintln!("Invalid hex payload: {payload}");
+        return ExitCode::FAILURE;
+    };
+
+    let (advertisement, device_type) = match uuid.parse_advertisement(&payload) {
+        Ok(advertisement) => advertisement.into_parts(),
+        Err(err) => {
+            eprintln!("Failed to parse advertisement: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match device_type {
+        Some(device_type) => println!(
+            "Device: {} {} ({})",
+            device_type.manufacturer, device_type.name, device_type.model
+        ),
+        None => println!("Device: unknown"),
+    }
+    for event in advertisement.iter_sensor_events() {
+        println!("{event}");
+    }
+    ExitCode::SUCCESS
+}
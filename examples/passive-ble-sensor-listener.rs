@@ -10,10 +10,43 @@
 //! then prints them to stdout.
 
 use btleplug::api::{Central, CentralEvent, Manager as _, Peripheral, ScanFilter};
-use btleplug::platform::Manager;
+use btleplug::platform::{Adapter, Manager, PeripheralId};
 use futures::stream::StreamExt;
 use std::error::Error;
-use xiaomi_ble::parse_service_advertisement;
+use xiaomi_ble::mibeacon::MacAddress;
+use xiaomi_ble::service::ServiceAdvertisement;
+use xiaomi_ble::{parse_manufacturer_data, parse_service_advertisement};
+
+/// Print the senders's MAC address, name and the contained sensor values.
+async fn print_advertisements(
+    central: &Adapter,
+    id: &PeripheralId,
+    advertisements: Vec<ServiceAdvertisement>,
+) -> Result<(), Box<dyn Error>> {
+    let peripheral = central.peripheral(id).await?;
+    // Prefer the MAC address included in the advertisement, because the peripheral address may
+    // be randomized. Both are in display order, so they print the same way.
+    let mac_address = advertisements
+        .iter()
+        .find_map(ServiceAdvertisement::mac_address)
+        .map_or_else(|| MacAddress::from(peripheral.address()), MacAddress::from);
+    println!("MAC: {}", mac_address);
+    let properties = peripheral.properties().await?;
+    properties
+        .as_ref()
+        .and_then(|p| p.local_name.as_ref())
+        .inspect(|local_name| println!("Name: {}", local_name));
+    properties
+        .as_ref()
+        .and_then(|p| p.rssi)
+        .inspect(|local_name| println!("RSSI: {}", local_name));
+
+    for advertisement in advertisements {
+        println!("{}", advertisement);
+    }
+    println!();
+    Ok(())
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
@@ -33,33 +66,36 @@ async fn main() -> Result<(), Box<dyn Error>> {
     // Start scanning for devices.
     central.start_scan(ScanFilter::default()).await?;
 
-    // When getting a ServiceDataAdvertisement, print the senders's MAC address, name and the
-    // contained sensor values.
+    // When getting a ServiceDataAdvertisement or ManufacturerDataAdvertisement, print the parsed
+    // advertisements.
     while let Some(event) = events.next().await {
-        if let CentralEvent::ServiceDataAdvertisement { id, service_data } = &event {
-            let mut service_advertisements = service_data
-                .iter()
-                .filter_map(|(uuid, data)| parse_service_advertisement(uuid, data).ok())
-                .peekable();
-            if service_advertisements.peek().is_some() {
-                println!("{:02X?}", &service_data);
-                let peripheral = central.peripheral(id).await?;
-                println!("MAC: {}", peripheral.address());
-                let properties = peripheral.properties().await?;
-                properties
-                    .as_ref()
-                    .and_then(|p| p.local_name.as_ref())
-                    .inspect(|local_name| println!("Name: {}", local_name));
-                properties
-                    .as_ref()
-                    .and_then(|p| p.rssi)
-                    .inspect(|local_name| println!("RSSI: {}", local_name));
-
-                for service_advertisement in service_advertisements {
-                    dbg!(&service_advertisement);
+        match &event {
+            CentralEvent::ServiceDataAdvertisement { id, service_data } => {
+                let advertisements: Vec<_> = service_data
+                    .iter()
+                    .filter_map(|(uuid, data)| parse_service_advertisement(uuid, data).ok())
+                    .collect();
+                if !advertisements.is_empty() {
+                    println!("{:02X?}", &service_data);
+                    print_advertisements(&central, id, advertisements).await?;
+                }
+            }
+            CentralEvent::ManufacturerDataAdvertisement {
+                id,
+                manufacturer_data,
+            } => {
+                let advertisements: Vec<_> = manufacturer_data
+                    .iter()
+                    .filter_map(|(company_id, data)| {
+                        parse_manufacturer_data(*company_id, data).ok()
+                    })
+                    .collect();
+                if !advertisements.is_empty() {
+                    println!("{:02X?}", &manufacturer_data);
+                    print_advertisements(&central, id, advertisements).await?;
                 }
-                println!()
             }
+            _ => {}
         }
     }
     Ok(())
@@ -0,0 +1,131 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Implementation of the ATC1441 custom firmware data structures.
+//!
+//! ## References
+//!
+//! - <https://github.com/atc1441/ATC_MiThermometer#advertising-format-of-the-custom-firmware>
+//! - <https://github.com/Ernst79/bleparser/blob/c42ae922e1abed2720c7fac993777e1bd59c0c93/package/bleparser/atc.py>
+
+use crate::device::DeviceType;
+use crate::mibeacon::MacAddress;
+use crate::sensor::{NumericMeasurementType, SensorEvent, UnitOfMeasurement};
+use crate::ParseError;
+use alloc::borrow::Cow;
+use alloc::vec;
+use binrw::io::Cursor;
+use binrw::BinRead;
+
+pub(crate) const ATC_DEVICE: DeviceType = DeviceType {
+    name: Cow::Borrowed("Temperature/Humidity Sensor"),
+    model: Cow::Borrowed("ATC"),
+    manufacturer: Cow::Borrowed("Xiaomi"),
+};
+
+/// Service Advertisement in the ATC1441 custom firmware format.
+#[derive(BinRead, Clone, PartialEq, Eq, Debug)]
+#[br(big)]
+pub struct AtcServiceAdvertisement {
+    /// MAC Address
+    pub mac_address: MacAddress,
+    /// Temperature (0.1 °C)
+    pub temperature_decicelsius: i16,
+    /// Humidity (%)
+    pub humidity_percent: u8,
+    /// Battery Power (%)
+    pub battery_percent: u8,
+    /// Battery Voltage (mV)
+    pub battery_millivolts: u16,
+    /// Frame Packet Counter
+    pub frame_counter: u8,
+}
+
+impl AtcServiceAdvertisement {
+    /// Length of the service advertisement payload (in bytes).
+    pub const LENGTH: usize = 13;
+
+    /// Parses a [AtcServiceAdvertisement] from a byte slice.
+    pub fn from_slice(slice: &[u8]) -> Result<Self, ParseError> {
+        Self::read(&mut Cursor::new(slice))
+            .map_err(|err| ParseError::from_binrw(err, slice.len(), Some(Self::LENGTH)))
+    }
+
+    /// Get device type of advertisement sender.
+    #[must_use]
+    pub const fn device_type(&self) -> &'static DeviceType {
+        &ATC_DEVICE
+    }
+
+    /// Yields a list of sensor events parsed from the objects contained in the service advertisement.
+    pub fn iter_sensor_events(&self) -> impl Iterator<Item = SensorEvent> + '_ {
+        vec![
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Temperature,
+                value: f64::from(self.temperature_decicelsius) / 10.0,
+                unit: UnitOfMeasurement::DegreesCelsius,
+            },
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Humidity,
+                value: f64::from(self.humidity_percent),
+                unit: UnitOfMeasurement::Percent,
+            },
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::BatteryPower,
+                value: f64::from(self.battery_percent),
+                unit: UnitOfMeasurement::Percent,
+            },
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Voltage,
+                value: f64::from(self.battery_millivolts) / 1000.0,
+                unit: UnitOfMeasurement::Volt,
+            },
+        ]
+        .into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AtcServiceAdvertisement;
+    use crate::sensor::{NumericMeasurementType, SensorEvent, UnitOfMeasurement};
+
+    const LYWSD03MMC_ATC_READING: [u8; 13] = [
+        0xA4, 0xC1, 0x38, 0x2C, 0x6F, 0xA1, 0x00, 0xD9, 0x35, 0x5D, 0x0B, 0xA3, 0x2F,
+    ];
+
+    #[test]
+    fn parse_lywsd03mmc_atc_reading() {
+        let message = AtcServiceAdvertisement::from_slice(&LYWSD03MMC_ATC_READING).unwrap();
+        assert_eq!(message.mac_address.as_slice(), &LYWSD03MMC_ATC_READING[..6]);
+        assert_eq!(message.temperature_decicelsius, 217);
+        assert_eq!(message.humidity_percent, 53);
+        assert_eq!(message.battery_percent, 93);
+        assert_eq!(message.battery_millivolts, 2979);
+        assert_eq!(message.frame_counter, 0x2F);
+
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(events.len(), 4);
+        assert!(matches!(
+            events[0],
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Temperature,
+                value,
+                unit: UnitOfMeasurement::DegreesCelsius,
+            } if value == 21.7
+        ));
+        assert!(matches!(
+            events[3],
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Voltage,
+                value,
+                unit: UnitOfMeasurement::Volt,
+            } if value == 2.979
+        ));
+    }
+}
@@ -8,13 +8,394 @@
 
 //! Information about BLE devices.
 
+use crate::sensor::{
+    BinaryMeasurementType as Binary, MeasurementKind, NumericMeasurementType as Numeric,
+};
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+const CLIMATE_SENSOR_MEASUREMENTS: &[MeasurementKind] = &[
+    MeasurementKind::Numeric(Numeric::Temperature),
+    MeasurementKind::Numeric(Numeric::Humidity),
+    MeasurementKind::Numeric(Numeric::BatteryPower),
+];
+const ATC_MEASUREMENTS: &[MeasurementKind] = &[
+    MeasurementKind::Numeric(Numeric::Temperature),
+    MeasurementKind::Numeric(Numeric::Humidity),
+    MeasurementKind::Numeric(Numeric::BatteryPower),
+    MeasurementKind::Numeric(Numeric::Voltage),
+];
+const PVVX_MEASUREMENTS: &[MeasurementKind] = &[
+    MeasurementKind::Numeric(Numeric::Temperature),
+    MeasurementKind::Numeric(Numeric::Humidity),
+    MeasurementKind::Numeric(Numeric::BatteryPower),
+    MeasurementKind::Numeric(Numeric::Voltage),
+    MeasurementKind::Binary(Binary::ReedSwitch),
+    MeasurementKind::Binary(Binary::Trigger),
+];
+const HHCCJCY01_MEASUREMENTS: &[MeasurementKind] = &[
+    MeasurementKind::Numeric(Numeric::Temperature),
+    MeasurementKind::Numeric(Numeric::Illuminance),
+    MeasurementKind::Numeric(Numeric::Moisture),
+    MeasurementKind::Numeric(Numeric::Conductivity),
+];
+const HHCCJCY10_MEASUREMENTS: &[MeasurementKind] = &[
+    MeasurementKind::Numeric(Numeric::Moisture),
+    MeasurementKind::Numeric(Numeric::Temperature),
+    MeasurementKind::Numeric(Numeric::Illuminance),
+    MeasurementKind::Numeric(Numeric::BatteryPower),
+];
+const HHCCPOT002_MEASUREMENTS: &[MeasurementKind] = &[
+    MeasurementKind::Numeric(Numeric::Moisture),
+    MeasurementKind::Numeric(Numeric::Conductivity),
+];
+const JQJCY01YM_MEASUREMENTS: &[MeasurementKind] = &[
+    MeasurementKind::Numeric(Numeric::Temperature),
+    MeasurementKind::Numeric(Numeric::Humidity),
+    MeasurementKind::Numeric(Numeric::FormaldehydeConcentration),
+    MeasurementKind::Numeric(Numeric::BatteryPower),
+];
+const WX08ZM_MEASUREMENTS: &[MeasurementKind] = &[
+    MeasurementKind::Binary(Binary::Switch),
+    MeasurementKind::Numeric(Numeric::RemainingSupplies),
+    MeasurementKind::Numeric(Numeric::BatteryPower),
+];
+const SCALE_MEASUREMENTS: &[MeasurementKind] = &[MeasurementKind::Numeric(Numeric::Weight)];
+const BODY_COMPOSITION_SCALE_MEASUREMENTS: &[MeasurementKind] = &[
+    MeasurementKind::Numeric(Numeric::Weight),
+    MeasurementKind::Numeric(Numeric::Impedance),
+];
+
 /// The BLE device type.
-#[derive(Debug)]
+///
+/// The built-in device types borrow their strings, but device types can also own them (e.g. when
+/// they are registered at runtime, see [`DeviceRegistry`]).
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceType {
     /// Device Name
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     /// Model number
-    pub model: &'static str,
+    pub model: Cow<'static, str>,
     /// Device Manufacturer
-    pub manufacturer: &'static str,
+    pub manufacturer: Cow<'static, str>,
+}
+
+/// Iterate over the built-in device types of all supported protocols.
+fn builtin_device_types() -> impl Iterator<Item = &'static DeviceType> {
+    static STANDALONE_DEVICE_TYPES: [DeviceType; 3] = [
+        crate::atc::ATC_DEVICE,
+        crate::pvvx::PVVX_DEVICE,
+        crate::hhccjcy10::HHCCJCY10_DEVICE,
+    ];
+
+    crate::mibeacon::DEVICE_TYPES
+        .values()
+        .chain(crate::miscale::DEVICE_TYPES.values())
+        .chain(STANDALONE_DEVICE_TYPES.iter())
+}
+
+/// Look up a device type by its model string (e.g. `"LYWSD03MMC"`).
+///
+/// This scans the device tables of all supported protocols and returns the first match.
+#[must_use]
+pub fn device_by_model(model: &str) -> Option<&'static DeviceType> {
+    builtin_device_types().find(|device_type| device_type.model == model)
+}
+
+impl DeviceType {
+    /// Get the category of this device (derived from the model).
+    #[must_use]
+    pub fn category(&self) -> DeviceCategory {
+        match self.model.as_ref() {
+            "ATC" | "CGC1" | "CGD1" | "CGDK2" | "CGG1" | "CGG1-ENCRYPTED" | "LYWSD02"
+            | "LYWSD02MMC" | "LYWSD03MMC" | "LYWSDCGQ" | "MHO-C303" | "MHO-C401" | "MJWSD05MMC"
+            | "PVVX" | "XMWSDJ04MMC" => DeviceCategory::ClimateSensor,
+            "GCLS002" | "HHCCJCY01" | "HHCCJCY10" | "HHCCPOT002" => DeviceCategory::PlantSensor,
+            "JQJCY01YM" => DeviceCategory::AirQualitySensor,
+            "CGPR1" | "HS1BB(MI)" | "RTCGQ02LM" | "XMPIRO2SXS" => DeviceCategory::MotionSensor,
+            "CGH1" | "MCCGQ02HL" | "MS1BB(MI)" => DeviceCategory::DoorSensor,
+            "JTYJGD03MI" | "RS1BB(MI)" | "SJWS01LM" => DeviceCategory::SafetySensor,
+            "DSL-C08" | "Lockin-SV40" | "MJZNMSQ01YD" | "XMZNMS04LM" | "XMZNMS08LM"
+            | "XMZNMST02YD" | "ZNMS16LM" | "ZNMS17LM" => DeviceCategory::Lock,
+            "K9B-1BTN" | "K9B-2BTN" | "K9B-3BTN" | "K9BB-1BTN" | "PTX_YK1_QMIMB" | "XMWXKG01LM"
+            | "XMWXKG01YL" | "YLAI003" | "YLKG07YL/YLKG08YL" => DeviceCategory::Switch,
+            "XMMF01JQD" | "YLYB01YL-BHFRC" | "YLYK01YL" | "YLYK01YL-FANCL" | "YLYK01YL-VENFAN" => {
+                DeviceCategory::Remote
+            }
+            "XMTZC01HM/XMTZC04HM" | "XMTZC02HM/XMTZC05HM/NUN4049CN" => DeviceCategory::Scale,
+            "MJYD02YL" | "MUE4094RT" => DeviceCategory::Light,
+            "MMC-T201-1" | "MMC-W505" => DeviceCategory::Thermometer,
+            "M1S-T500" | "T700" => DeviceCategory::Toothbrush,
+            "V-SK152" | "YM-K1501" | "YM-K1501EU" => DeviceCategory::Kettle,
+            "SU001-T" | "WX08ZM" => DeviceCategory::Appliance,
+            _ => DeviceCategory::Other,
+        }
+    }
+
+    /// Get the measurements that this device is expected to report (e.g. for creating entities
+    /// in a UI before the first advertisement is received).
+    ///
+    /// This is a curated list that only covers the sensors that periodically report
+    /// measurements. For other devices (e.g. locks or buttons, which report discrete events), an
+    /// empty slice is returned.
+    #[must_use]
+    pub fn expected_measurements(&self) -> &'static [MeasurementKind] {
+        match self.model.as_ref() {
+            "CGC1" | "CGD1" | "CGDK2" | "CGG1" | "CGG1-ENCRYPTED" | "LYWSD02" | "LYWSD02MMC"
+            | "LYWSD03MMC" | "LYWSDCGQ" | "MHO-C303" | "MHO-C401" | "MJWSD05MMC"
+            | "XMWSDJ04MMC" => CLIMATE_SENSOR_MEASUREMENTS,
+            "ATC" => ATC_MEASUREMENTS,
+            "PVVX" => PVVX_MEASUREMENTS,
+            "HHCCJCY01" | "GCLS002" => HHCCJCY01_MEASUREMENTS,
+            "HHCCJCY10" => HHCCJCY10_MEASUREMENTS,
+            "HHCCPOT002" => HHCCPOT002_MEASUREMENTS,
+            "JQJCY01YM" => JQJCY01YM_MEASUREMENTS,
+            "WX08ZM" => WX08ZM_MEASUREMENTS,
+            "XMTZC01HM/XMTZC04HM" => SCALE_MEASUREMENTS,
+            "XMTZC02HM/XMTZC05HM/NUN4049CN" => BODY_COMPOSITION_SCALE_MEASUREMENTS,
+            _ => &[],
+        }
+    }
+}
+
+/// Lookup table for MiBeacon device types that can be extended at runtime.
+///
+/// Device types registered with [`DeviceRegistry::register`] take precedence over the built-in
+/// device types, so that devices which are not supported by this crate yet (or which are
+/// misidentified) can be used without forking.
+#[derive(Clone, Debug, Default)]
+pub struct DeviceRegistry {
+    custom_device_types: BTreeMap<u16, DeviceType>,
+}
+
+impl DeviceRegistry {
+    /// Create a registry that only contains the built-in device types.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a custom device type for a MiBeacon device ID.
+    ///
+    /// Returns the custom device type that was previously registered for this ID (if any).
+    pub fn register(&mut self, device_id: u16, device_type: DeviceType) -> Option<DeviceType> {
+        self.custom_device_types.insert(device_id, device_type)
+    }
+
+    /// Look up the device type for a MiBeacon device ID, preferring custom device types over the
+    /// built-in ones.
+    #[must_use]
+    pub fn lookup(&self, device_id: u16) -> Option<&DeviceType> {
+        self.custom_device_types
+            .get(&device_id)
+            .or_else(|| crate::mibeacon::DEVICE_TYPES.get(&device_id))
+    }
+}
+
+/// The category of a BLE device (e.g. for grouping devices in a UI).
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+pub enum DeviceCategory {
+    /// Temperature/Humidity Sensor
+    ClimateSensor,
+    /// Plant Sensor
+    PlantSensor,
+    /// Air Quality Sensor
+    AirQualitySensor,
+    /// Motion Sensor
+    MotionSensor,
+    /// Door/Window Sensor
+    DoorSensor,
+    /// Smoke Detector or Flood Sensor
+    SafetySensor,
+    /// Door Lock
+    Lock,
+    /// Switch or Button
+    Switch,
+    /// Remote Control
+    Remote,
+    /// Scale
+    Scale,
+    /// Light
+    Light,
+    /// Body Thermometer
+    Thermometer,
+    /// Toothbrush
+    Toothbrush,
+    /// Kettle
+    Kettle,
+    /// Other Household Appliance (e.g. Mosquito Repellent)
+    Appliance,
+    /// Other Device
+    Other,
+}
+
+impl DeviceCategory {
+    /// Get the lowercase name of this category.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match &self {
+            Self::ClimateSensor => "climate_sensor",
+            Self::PlantSensor => "plant_sensor",
+            Self::AirQualitySensor => "air_quality_sensor",
+            Self::MotionSensor => "motion_sensor",
+            Self::DoorSensor => "door_sensor",
+            Self::SafetySensor => "safety_sensor",
+            Self::Lock => "lock",
+            Self::Switch => "switch",
+            Self::Remote => "remote",
+            Self::Scale => "scale",
+            Self::Light => "light",
+            Self::Thermometer => "thermometer",
+            Self::Toothbrush => "toothbrush",
+            Self::Kettle => "kettle",
+            Self::Appliance => "appliance",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for DeviceCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        builtin_device_types, device_by_model, DeviceCategory, DeviceRegistry, DeviceType,
+    };
+    use crate::sensor::{MeasurementKind, NumericMeasurementType};
+    use alloc::borrow::Cow;
+    use alloc::string::String;
+
+    fn category_of(model: &'static str) -> DeviceCategory {
+        DeviceType {
+            name: Cow::Borrowed(""),
+            model: Cow::Borrowed(model),
+            manufacturer: Cow::Borrowed("Xiaomi"),
+        }
+        .category()
+    }
+
+    #[test]
+    fn device_category_from_model() {
+        assert_eq!(category_of("LYWSD03MMC"), DeviceCategory::ClimateSensor);
+        assert_eq!(category_of("HHCCJCY01"), DeviceCategory::PlantSensor);
+        assert_eq!(category_of("XMZNMS04LM"), DeviceCategory::Lock);
+        assert_eq!(category_of("K9B-1BTN"), DeviceCategory::Switch);
+        assert_eq!(category_of("XMTZC01HM/XMTZC04HM"), DeviceCategory::Scale);
+        assert_eq!(category_of("MJYD02YL"), DeviceCategory::Light);
+        assert_eq!(category_of("WX08ZM"), DeviceCategory::Appliance);
+        assert_eq!(category_of("UNKNOWN"), DeviceCategory::Other);
+    }
+
+    #[test]
+    fn builtin_device_types_have_category() {
+        for device_type in builtin_device_types() {
+            assert_ne!(
+                device_type.category(),
+                DeviceCategory::Other,
+                "{} has no category",
+                device_type.model
+            );
+        }
+    }
+
+    #[test]
+    fn builtin_sensors_have_expected_measurements() {
+        for device_type in builtin_device_types() {
+            if matches!(
+                device_type.category(),
+                DeviceCategory::ClimateSensor
+                    | DeviceCategory::PlantSensor
+                    | DeviceCategory::AirQualitySensor
+                    | DeviceCategory::Scale
+            ) {
+                assert!(
+                    !device_type.expected_measurements().is_empty(),
+                    "{} has no expected measurements",
+                    device_type.model
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn device_registry() {
+        let mut registry = DeviceRegistry::new();
+        assert_eq!(registry.lookup(0x055B).unwrap().model, "LYWSD03MMC");
+        assert!(registry.lookup(0xFFFF).is_none());
+
+        let custom_device_type = DeviceType {
+            name: Cow::Borrowed("Custom Sensor"),
+            model: Cow::Borrowed("CUSTOM"),
+            manufacturer: Cow::Borrowed("Custom"),
+        };
+        assert_eq!(registry.register(0xFFFF, custom_device_type.clone()), None);
+        assert_eq!(registry.lookup(0xFFFF), Some(&custom_device_type));
+
+        // Custom device types take precedence over the built-in ones.
+        registry.register(0x055B, custom_device_type.clone());
+        assert_eq!(registry.lookup(0x055B), Some(&custom_device_type));
+    }
+
+    #[test]
+    fn owned_device_type() {
+        let model = String::from("LYWSD03MMC");
+        let device_type = DeviceType {
+            name: Cow::Owned(String::from("Temperature/Humidity Sensor")),
+            model: Cow::Owned(model),
+            manufacturer: Cow::Owned(String::from("Xiaomi")),
+        };
+        assert_eq!(device_type.category(), DeviceCategory::ClimateSensor);
+        assert_eq!(&device_type, device_by_model("LYWSD03MMC").unwrap());
+    }
+
+    #[test]
+    fn lookup_device_by_model() {
+        let device_type = device_by_model("LYWSD03MMC").unwrap();
+        assert_eq!(device_type.name, "Temperature/Humidity Sensor");
+        assert_eq!(device_type.manufacturer, "Xiaomi");
+        assert_eq!(device_by_model("HHCCJCY10").unwrap().model, "HHCCJCY10");
+        assert!(device_by_model("UNKNOWN").is_none());
+    }
+
+    #[test]
+    fn expected_measurements() {
+        assert_eq!(
+            device_by_model("LYWSD03MMC")
+                .unwrap()
+                .expected_measurements(),
+            [
+                MeasurementKind::Numeric(NumericMeasurementType::Temperature),
+                MeasurementKind::Numeric(NumericMeasurementType::Humidity),
+                MeasurementKind::Numeric(NumericMeasurementType::BatteryPower),
+            ]
+        );
+        assert!(device_by_model("XMTZC02HM/XMTZC05HM/NUN4049CN")
+            .unwrap()
+            .expected_measurements()
+            .contains(&MeasurementKind::Numeric(NumericMeasurementType::Impedance)));
+        assert!(device_by_model("XMZNMS04LM")
+            .unwrap()
+            .expected_measurements()
+            .is_empty());
+    }
+
+    #[test]
+    fn device_type_as_set_member() {
+        use std::collections::HashSet;
+
+        let mut device_types = HashSet::new();
+        assert!(device_types.insert(device_by_model("LYWSD03MMC").unwrap()));
+        assert!(device_types.insert(device_by_model("HHCCJCY10").unwrap()));
+        assert!(!device_types.insert(device_by_model("LYWSD03MMC").unwrap()));
+        assert_eq!(device_types.len(), 2);
+
+        let cloned = device_by_model("HHCCJCY10").unwrap().clone();
+        assert!(device_types.contains(&cloned));
+    }
 }
@@ -17,18 +17,20 @@ use crate::device::DeviceType;
 use crate::sensor::{NumericMeasurementType, SensorEvent, UnitOfMeasurement};
 use crate::util::U24;
 use crate::ParseError;
+use alloc::borrow::Cow;
+use alloc::vec;
+use binrw::io::Cursor;
 use binrw::BinRead;
-use std::io::Cursor;
 
-const HHCCJCY10_DEVICE: DeviceType = DeviceType {
-    name: "Plant Sensor",
-    model: "HHCCJCY10",
-    manufacturer: "HHCC Plant Technology Co. Ltd",
+pub(crate) const HHCCJCY10_DEVICE: DeviceType = DeviceType {
+    name: Cow::Borrowed("Plant Sensor"),
+    model: Cow::Borrowed("HHCCJCY10"),
+    manufacturer: Cow::Borrowed("HHCC Plant Technology Co. Ltd"),
 };
 
 /// Service Advertisement in the HHCCJCY10 Plant Sensor (Pink Version) format.
 #[allow(dead_code)]
-#[derive(BinRead, Debug)]
+#[derive(BinRead, Clone, PartialEq, Eq, Debug)]
 #[br(little)]
 pub struct HHCCJCY10ServiceAdvertisement {
     #[allow(dead_code)]
@@ -46,9 +48,13 @@ pub struct HHCCJCY10ServiceAdvertisement {
 }
 
 impl HHCCJCY10ServiceAdvertisement {
+    /// Length of the service advertisement payload (in bytes).
+    pub const LENGTH: usize = 13;
+
     /// Parses a [HHCCJCY10ServiceAdvertisement] from a byte slice.
     pub fn from_slice(slice: &[u8]) -> Result<Self, ParseError> {
-        Ok(Self::read(&mut Cursor::new(slice))?)
+        Self::read(&mut Cursor::new(slice))
+            .map_err(|err| ParseError::from_binrw(err, slice.len(), Some(Self::LENGTH)))
     }
 
     /// Get device type of advertisement sender.
@@ -7,7 +7,29 @@
 // SPDX-License-Identifier: MPL-2.0
 
 //! Library for parsing Xiamo BLE data structures.
+//!
+//! ## Features
+//!
+//! - `std` (enabled by default): Use the standard library. Without this feature, the crate is
+//!   `no_std` (but still requires `alloc`). All parsing APIs remain available, but
+//!   [`ParseError`] does not expose the underlying `binrw` error as its
+//!   [`source`](core::error::Error::source).
+//! - `logging` (enabled by default): Log warnings (e.g. about unhandled MiBeacon objects) via the
+//!   [`log`](https://docs.rs/log) crate.
+//! - `chrono`: Conversion of MiBeacon timestamps to [`chrono`](https://docs.rs/chrono) types.
+//! - `serde`: Serialization support for sensor events and device types.
+//! - `raw-payload`: Keep the raw bytes of every parsed MiBeacon object (see
+//!   [`MiBeaconObject::raw_payload_bytes`](mibeacon::MiBeaconObject::raw_payload_bytes)), e.g. for
+//!   debugging misbehaving devices.
+//! - `test-util`: Helpers for writing tests, e.g.
+//!   [`MiBeaconBuilder`](mibeacon::MiBeaconBuilder) to create MiBeacon advertisements without
+//!   writing the raw bytes by hand.
+//! - `bluetooth`: Conversions between [`btleplug`](https://docs.rs/btleplug) types and the types in
+//!   this crate (e.g. `BDAddr` to [`MacAddress`](mibeacon::MacAddress)). Also needed to build the
+//!   `passive-ble-sensor-listener` example, which uses `btleplug` to scan for advertisements.
+//!   Without this feature, the library does not depend on any BLE stack.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_debug_implementations)]
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
@@ -22,13 +44,21 @@
 #![cfg_attr(not(debug_assertions), warn(clippy::used_underscore_binding))]
 #![cfg_attr(not(test), deny(clippy::panic_in_result_fn))]
 
+extern crate alloc;
+
+pub mod atc;
 pub mod device;
 pub mod hhccjcy10;
 pub mod mibeacon;
 pub mod miscale;
+pub mod prelude;
+pub mod pvvx;
 pub mod sensor;
 pub mod service;
 mod util;
 
-pub use service::parse_service_advertisement;
-pub use util::ParseError;
+pub use service::{
+    looks_like_xiaomi, parse_all_service_data, parse_manufacturer_data,
+    parse_service_advertisement, parse_service_advertisement_u16, strip_ad_header, try_parse_any,
+};
+pub use util::{ParseError, U24OutOfRangeError, U24};
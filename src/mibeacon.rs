@@ -20,84 +20,136 @@
 #![allow(clippy::must_use_candidate)]
 
 use crate::device::DeviceType;
-use crate::sensor::{NumericMeasurementType, SensorEvent, UnitOfMeasurement};
-use crate::util::U24;
+use crate::sensor::{
+    BinaryMeasurementType, DiscreteEvent, NumericMeasurementType, Reading, SensorEvent,
+    UnitOfMeasurement,
+};
+use crate::util::{log_warn, MeshUnsupportedError, ObjectLengthError, TooManyObjectsError, U24};
 use crate::ParseError;
-use binrw::{binread, helpers::until_eof, BinRead};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use binrw::io::Cursor;
+use binrw::io::{Read, Seek, SeekFrom};
+use binrw::{binrw, BinRead, BinResult, BinWrite};
+use core::borrow::Borrow;
 use core::fmt;
-use log::warn;
+use core::str::FromStr;
 use modular_bitfield::prelude::*;
-use phf::phf_map;
-use std::io::Cursor;
-
-static DEVICE_TYPES: phf::Map<u16, DeviceType> = phf_map! {
-    0x0C3Cu16 => DeviceType { name: "Alarm Clock", model: "CGC1", manufacturer: "Xiaomi" },
-    0x0576u16 => DeviceType { name: "3-in-1 Alarm Clock", model: "CGD1", manufacturer: "Xiaomi" },
-    0x066Fu16 => DeviceType { name: "Temperature/Humidity Sensor", model: "CGDK2", manufacturer: "Xiaomi" },
-    0x0347u16 => DeviceType { name: "Temperature/Humidity Sensor", model: "CGG1", manufacturer: "Xiaomi" },
-    0x0B48u16 => DeviceType { name: "Temperature/Humidity Sensor", model: "CGG1-ENCRYPTED", manufacturer: "Xiaomi" },
-    0x03D6u16 => DeviceType { name: "Door/Window Sensor", model: "CGH1", manufacturer: "Xiaomi" },
-    0x0A83u16 => DeviceType { name: "Motion/Light Sensor", model: "CGPR1", manufacturer: "Xiaomi" },
-    0x03BCu16 => DeviceType { name: "Grow Care Garden", model: "GCLS002", manufacturer: "Xiaomi" },
-    0x0098u16 => DeviceType { name: "Plant Sensor", model: "HHCCJCY01", manufacturer: "Xiaomi" },
-    0x015Du16 => DeviceType { name: "Smart Flower Pot", model: "HHCCPOT002", manufacturer: "Xiaomi" },
-    0x02DFu16 => DeviceType { name: "Formaldehyde Sensor", model: "JQJCY01YM", manufacturer: "Xiaomi" },
-    0x0997u16 => DeviceType { name: "Smoke Detector", model: "JTYJGD03MI", manufacturer: "Xiaomi" },
-    0x1568u16 => DeviceType { name: "Switch (single button)", model: "K9B-1BTN", manufacturer: "Xiaomi" },
-    0x1569u16 => DeviceType { name: "Switch (double button)", model: "K9B-2BTN", manufacturer: "Xiaomi" },
-    0x0DFDu16 => DeviceType { name: "Switch (triple button)", model: "K9B-3BTN", manufacturer: "Xiaomi" },
-    0x1C10u16 => DeviceType { name: "Switch (single button)", model: "K9BB-1BTN", manufacturer: "Xiaomi" },
-    0x1889u16 => DeviceType { name: "Door/Window Sensor", model: "MS1BB(MI)", manufacturer: "Xiaomi" },
-    0x2AEBu16 => DeviceType { name: "Motion Sensor", model: "HS1BB(MI)", manufacturer: "Xiaomi" },
-    0x3F0Fu16 => DeviceType { name: "Flood and Rain Sensor", model: "RS1BB(MI)", manufacturer: "Xiaomi" },
-    0x01AAu16 => DeviceType { name: "Temperature/Humidity Sensor", model: "LYWSDCGQ", manufacturer: "Xiaomi" },
-    0x045Bu16 => DeviceType { name: "Temperature/Humidity Sensor", model: "LYWSD02", manufacturer: "Xiaomi" },
-    0x16E4u16 => DeviceType { name: "Temperature/Humidity Sensor", model: "LYWSD02MMC", manufacturer: "Xiaomi" },
-    0x2542u16 => DeviceType { name: "Temperature/Humidity Sensor", model: "LYWSD02MMC", manufacturer: "Xiaomi" },
-    0x055Bu16 => DeviceType { name: "Temperature/Humidity Sensor", model: "LYWSD03MMC", manufacturer: "Xiaomi" },
-    0x2832u16 => DeviceType { name: "Temperature/Humidity Sensor", model: "MJWSD05MMC", manufacturer: "Xiaomi" },
-    0x098Bu16 => DeviceType { name: "Door/Window Sensor", model: "MCCGQ02HL", manufacturer: "Xiaomi" },
-    0x06D3u16 => DeviceType { name: "Alarm Clock", model: "MHO-C303", manufacturer: "Xiaomi" },
-    0x0387u16 => DeviceType { name: "Temperature/Humidity Sensor", model: "MHO-C401", manufacturer: "Xiaomi" },
-    0x07F6u16 => DeviceType { name: "Nightlight", model: "MJYD02YL", manufacturer: "Xiaomi" },
-    0x04E9u16 => DeviceType { name: "Door Lock", model: "MJZNMSQ01YD", manufacturer: "Xiaomi" },
-    0x00DBu16 => DeviceType { name: "Baby Thermometer", model: "MMC-T201-1", manufacturer: "Xiaomi" },
-    0x0391u16 => DeviceType { name: "Body Thermometer", model: "MMC-W505", manufacturer: "Xiaomi" },
-    0x03DDu16 => DeviceType { name: "Nightlight", model: "MUE4094RT", manufacturer: "Xiaomi" },
-    0x0489u16 => DeviceType { name: "Smart Toothbrush", model: "M1S-T500", manufacturer: "Xiaomi" },
-    0x0806u16 => DeviceType { name: "Smart Toothbrush", model: "T700", manufacturer: "Xiaomi" },
-    0x1790u16 => DeviceType { name: "Smart Toothbrush", model: "T700", manufacturer: "Xiaomi" },
-    0x0A8Du16 => DeviceType { name: "Motion Sensor", model: "RTCGQ02LM", manufacturer: "Xiaomi" },
-    0x3531u16 => DeviceType { name: "Motion Sensor", model: "XMPIRO2SXS", manufacturer: "Xiaomi" },
-    0x0863u16 => DeviceType { name: "Flood Detector", model: "SJWS01LM", manufacturer: "Xiaomi" },
-    0x045Cu16 => DeviceType { name: "Smart Kettle", model: "V-SK152", manufacturer: "Xiaomi" },
-    0x040Au16 => DeviceType { name: "Mosquito Repellent", model: "WX08ZM", manufacturer: "Xiaomi" },
-    0x04E1u16 => DeviceType { name: "Magic Cube", model: "XMMF01JQD", manufacturer: "Xiaomi" },
-    0x1203u16 => DeviceType { name: "Thermometer", model: "XMWSDJ04MMC", manufacturer: "Xiaomi" },
-    0x1949u16 => DeviceType { name: "Switch (double button)", model: "XMWXKG01YL", manufacturer: "Xiaomi" },
-    0x2387u16 => DeviceType { name: "Button", model: "XMWXKG01LM", manufacturer: "Xiaomi" },
-    0x098Cu16 => DeviceType { name: "Door Lock", model: "XMZNMST02YD", manufacturer: "Xiaomi" },
-    0x0784u16 => DeviceType { name: "Door Lock", model: "XMZNMS04LM", manufacturer: "Xiaomi" },
-    0x0E39u16 => DeviceType { name: "Door Lock", model: "XMZNMS08LM", manufacturer: "Xiaomi" },
-    0x07BFu16 => DeviceType { name: "Wireless Switch", model: "YLAI003", manufacturer: "Xiaomi" },
-    0x38BBu16 => DeviceType { name: "Wireless Switch", model: "PTX_YK1_QMIMB", manufacturer: "Xiaomi" },
-    0x0153u16 => DeviceType { name: "Remote Control", model: "YLYK01YL", manufacturer: "Xiaomi" },
-    0x068Eu16 => DeviceType { name: "Fan Remote Control", model: "YLYK01YL-FANCL", manufacturer: "Xiaomi" },
-    0x04E6u16 => DeviceType { name: "Ventilator Fan Remote Control", model: "YLYK01YL-VENFAN", manufacturer: "Xiaomi" },
-    0x03BFu16 => DeviceType { name: "Bathroom Heater Remote", model: "YLYB01YL-BHFRC", manufacturer: "Xiaomi" },
-    0x03B6u16 => DeviceType { name: "Dimmer Switch", model: "YLKG07YL/YLKG08YL", manufacturer: "Xiaomi" },
-    0x0083u16 => DeviceType { name: "Smart Kettle", model: "YM-K1501", manufacturer: "Xiaomi" },
-    0x0113u16 => DeviceType { name: "Smart Kettle", model: "YM-K1501EU", manufacturer: "Xiaomi" },
-    0x069Eu16 => DeviceType { name: "Door Lock", model: "ZNMS16LM", manufacturer: "Xiaomi" },
-    0x069Fu16 => DeviceType { name: "Door Lock", model: "ZNMS17LM", manufacturer: "Xiaomi" },
-    0x0380u16 => DeviceType { name: "Door Lock", model: "DSL-C08", manufacturer: "Xiaomi" },
-    0x11C2u16 => DeviceType { name: "Door Lock", model: "Lockin-SV40", manufacturer: "Xiaomi" },
-    0x0DE7u16 => DeviceType { name: "Odor Eliminator", model: "SU001-T", manufacturer: "Xiaomi" },
+use phf::{phf_map, phf_set};
+use thiserror::Error;
+
+pub(crate) static DEVICE_TYPES: phf::Map<u16, DeviceType> = phf_map! {
+    0x0C3Cu16 => DeviceType { name: Cow::Borrowed("Alarm Clock"), model: Cow::Borrowed("CGC1"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0576u16 => DeviceType { name: Cow::Borrowed("3-in-1 Alarm Clock"), model: Cow::Borrowed("CGD1"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x066Fu16 => DeviceType { name: Cow::Borrowed("Temperature/Humidity Sensor"), model: Cow::Borrowed("CGDK2"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0347u16 => DeviceType { name: Cow::Borrowed("Temperature/Humidity Sensor"), model: Cow::Borrowed("CGG1"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0B48u16 => DeviceType { name: Cow::Borrowed("Temperature/Humidity Sensor"), model: Cow::Borrowed("CGG1-ENCRYPTED"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x03D6u16 => DeviceType { name: Cow::Borrowed("Door/Window Sensor"), model: Cow::Borrowed("CGH1"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0A83u16 => DeviceType { name: Cow::Borrowed("Motion/Light Sensor"), model: Cow::Borrowed("CGPR1"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x03BCu16 => DeviceType { name: Cow::Borrowed("Grow Care Garden"), model: Cow::Borrowed("GCLS002"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0098u16 => DeviceType { name: Cow::Borrowed("Plant Sensor"), model: Cow::Borrowed("HHCCJCY01"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x015Du16 => DeviceType { name: Cow::Borrowed("Smart Flower Pot"), model: Cow::Borrowed("HHCCPOT002"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x02DFu16 => DeviceType { name: Cow::Borrowed("Formaldehyde Sensor"), model: Cow::Borrowed("JQJCY01YM"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0997u16 => DeviceType { name: Cow::Borrowed("Smoke Detector"), model: Cow::Borrowed("JTYJGD03MI"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x1568u16 => DeviceType { name: Cow::Borrowed("Switch (single button)"), model: Cow::Borrowed("K9B-1BTN"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x1569u16 => DeviceType { name: Cow::Borrowed("Switch (double button)"), model: Cow::Borrowed("K9B-2BTN"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0DFDu16 => DeviceType { name: Cow::Borrowed("Switch (triple button)"), model: Cow::Borrowed("K9B-3BTN"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x1C10u16 => DeviceType { name: Cow::Borrowed("Switch (single button)"), model: Cow::Borrowed("K9BB-1BTN"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x1889u16 => DeviceType { name: Cow::Borrowed("Door/Window Sensor"), model: Cow::Borrowed("MS1BB(MI)"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x2AEBu16 => DeviceType { name: Cow::Borrowed("Motion Sensor"), model: Cow::Borrowed("HS1BB(MI)"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x3F0Fu16 => DeviceType { name: Cow::Borrowed("Flood and Rain Sensor"), model: Cow::Borrowed("RS1BB(MI)"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x01AAu16 => DeviceType { name: Cow::Borrowed("Temperature/Humidity Sensor"), model: Cow::Borrowed("LYWSDCGQ"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x045Bu16 => DeviceType { name: Cow::Borrowed("Temperature/Humidity Sensor"), model: Cow::Borrowed("LYWSD02"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x16E4u16 => DeviceType { name: Cow::Borrowed("Temperature/Humidity Sensor"), model: Cow::Borrowed("LYWSD02MMC"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x2542u16 => DeviceType { name: Cow::Borrowed("Temperature/Humidity Sensor"), model: Cow::Borrowed("LYWSD02MMC"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x055Bu16 => DeviceType { name: Cow::Borrowed("Temperature/Humidity Sensor"), model: Cow::Borrowed("LYWSD03MMC"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x2832u16 => DeviceType { name: Cow::Borrowed("Temperature/Humidity Sensor"), model: Cow::Borrowed("MJWSD05MMC"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x098Bu16 => DeviceType { name: Cow::Borrowed("Door/Window Sensor"), model: Cow::Borrowed("MCCGQ02HL"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x06D3u16 => DeviceType { name: Cow::Borrowed("Alarm Clock"), model: Cow::Borrowed("MHO-C303"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0387u16 => DeviceType { name: Cow::Borrowed("Temperature/Humidity Sensor"), model: Cow::Borrowed("MHO-C401"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x07F6u16 => DeviceType { name: Cow::Borrowed("Nightlight"), model: Cow::Borrowed("MJYD02YL"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x04E9u16 => DeviceType { name: Cow::Borrowed("Door Lock"), model: Cow::Borrowed("MJZNMSQ01YD"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x00DBu16 => DeviceType { name: Cow::Borrowed("Baby Thermometer"), model: Cow::Borrowed("MMC-T201-1"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0391u16 => DeviceType { name: Cow::Borrowed("Body Thermometer"), model: Cow::Borrowed("MMC-W505"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x03DDu16 => DeviceType { name: Cow::Borrowed("Nightlight"), model: Cow::Borrowed("MUE4094RT"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0489u16 => DeviceType { name: Cow::Borrowed("Smart Toothbrush"), model: Cow::Borrowed("M1S-T500"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0806u16 => DeviceType { name: Cow::Borrowed("Smart Toothbrush"), model: Cow::Borrowed("T700"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x1790u16 => DeviceType { name: Cow::Borrowed("Smart Toothbrush"), model: Cow::Borrowed("T700"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0A8Du16 => DeviceType { name: Cow::Borrowed("Motion Sensor"), model: Cow::Borrowed("RTCGQ02LM"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x3531u16 => DeviceType { name: Cow::Borrowed("Motion Sensor"), model: Cow::Borrowed("XMPIRO2SXS"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0863u16 => DeviceType { name: Cow::Borrowed("Flood Detector"), model: Cow::Borrowed("SJWS01LM"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x045Cu16 => DeviceType { name: Cow::Borrowed("Smart Kettle"), model: Cow::Borrowed("V-SK152"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x040Au16 => DeviceType { name: Cow::Borrowed("Mosquito Repellent"), model: Cow::Borrowed("WX08ZM"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x04E1u16 => DeviceType { name: Cow::Borrowed("Magic Cube"), model: Cow::Borrowed("XMMF01JQD"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x1203u16 => DeviceType { name: Cow::Borrowed("Thermometer"), model: Cow::Borrowed("XMWSDJ04MMC"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x1949u16 => DeviceType { name: Cow::Borrowed("Switch (double button)"), model: Cow::Borrowed("XMWXKG01YL"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x2387u16 => DeviceType { name: Cow::Borrowed("Button"), model: Cow::Borrowed("XMWXKG01LM"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x098Cu16 => DeviceType { name: Cow::Borrowed("Door Lock"), model: Cow::Borrowed("XMZNMST02YD"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0784u16 => DeviceType { name: Cow::Borrowed("Door Lock"), model: Cow::Borrowed("XMZNMS04LM"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0E39u16 => DeviceType { name: Cow::Borrowed("Door Lock"), model: Cow::Borrowed("XMZNMS08LM"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x07BFu16 => DeviceType { name: Cow::Borrowed("Wireless Switch"), model: Cow::Borrowed("YLAI003"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x38BBu16 => DeviceType { name: Cow::Borrowed("Wireless Switch"), model: Cow::Borrowed("PTX_YK1_QMIMB"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0153u16 => DeviceType { name: Cow::Borrowed("Remote Control"), model: Cow::Borrowed("YLYK01YL"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x068Eu16 => DeviceType { name: Cow::Borrowed("Fan Remote Control"), model: Cow::Borrowed("YLYK01YL-FANCL"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x04E6u16 => DeviceType { name: Cow::Borrowed("Ventilator Fan Remote Control"), model: Cow::Borrowed("YLYK01YL-VENFAN"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x03BFu16 => DeviceType { name: Cow::Borrowed("Bathroom Heater Remote"), model: Cow::Borrowed("YLYB01YL-BHFRC"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x03B6u16 => DeviceType { name: Cow::Borrowed("Dimmer Switch"), model: Cow::Borrowed("YLKG07YL/YLKG08YL"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0083u16 => DeviceType { name: Cow::Borrowed("Smart Kettle"), model: Cow::Borrowed("YM-K1501"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0113u16 => DeviceType { name: Cow::Borrowed("Smart Kettle"), model: Cow::Borrowed("YM-K1501EU"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x069Eu16 => DeviceType { name: Cow::Borrowed("Door Lock"), model: Cow::Borrowed("ZNMS16LM"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x069Fu16 => DeviceType { name: Cow::Borrowed("Door Lock"), model: Cow::Borrowed("ZNMS17LM"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0380u16 => DeviceType { name: Cow::Borrowed("Door Lock"), model: Cow::Borrowed("DSL-C08"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x11C2u16 => DeviceType { name: Cow::Borrowed("Door Lock"), model: Cow::Borrowed("Lockin-SV40"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x0DE7u16 => DeviceType { name: Cow::Borrowed("Odor Eliminator"), model: Cow::Borrowed("SU001-T"), manufacturer: Cow::Borrowed("Xiaomi") },
+};
+
+/// Device IDs of devices that always send encrypted advertisements, so that a bindkey is needed to
+/// decrypt them.
+static ENCRYPTED_DEVICE_IDS: phf::Set<u16> = phf_set! {
+    0x0B48u16, // CGG1-ENCRYPTED
+    0x066Fu16, // CGDK2
+    0x0997u16, // JTYJGD03MI
+    0x1568u16, // K9B-1BTN
+    0x1569u16, // K9B-2BTN
+    0x0DFDu16, // K9B-3BTN
+    0x1C10u16, // K9BB-1BTN
+    0x1889u16, // MS1BB(MI)
+    0x2AEBu16, // HS1BB(MI)
+    0x3F0Fu16, // RS1BB(MI)
+    0x16E4u16, // LYWSD02MMC
+    0x2542u16, // LYWSD02MMC
+    0x055Bu16, // LYWSD03MMC
+    0x2832u16, // MJWSD05MMC
+    0x098Bu16, // MCCGQ02HL
+    0x0387u16, // MHO-C401
+    0x0A8Du16, // RTCGQ02LM
+    0x3531u16, // XMPIRO2SXS
+    0x1203u16, // XMWSDJ04MMC
+    0x1949u16, // XMWXKG01YL
+    0x2387u16, // XMWXKG01LM
+    0x0784u16, // XMZNMS04LM
+    0x0E39u16, // XMZNMS08LM
 };
 
+/// Returns `true` if devices with this ID are known to always encrypt their advertisements, so
+/// that a bindkey is required to read their sensor values.
+#[must_use]
+pub fn requires_bindkey(device_id: u16) -> bool {
+    ENCRYPTED_DEVICE_IDS.contains(&device_id)
+}
+
+/// Iterate over all known MiBeacon device types and their device IDs.
+pub fn all_device_types() -> impl Iterator<Item = (u16, &'static DeviceType)> {
+    DEVICE_TYPES
+        .entries()
+        .map(|(device_id, device_type)| (*device_id, device_type))
+}
+
 /// MAC Address of a device.
-#[derive(BinRead)]
-#[br(little)]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[brw(little)]
 pub struct MacAddress([u8; 6]);
 
 impl MacAddress {
@@ -110,9 +162,80 @@ impl MacAddress {
     pub fn into_inner(self) -> [u8; 6] {
         self.0
     }
+
+    /// Get the MAC address with its bytes in reverse order.
+    ///
+    /// MiBeacon and PVVX advertisements transmit the address least significant byte first, i.e.
+    /// in reverse display order.
+    pub(crate) fn reversed(mut self) -> Self {
+        self.0.reverse();
+        self
+    }
 }
 
-impl fmt::Debug for MacAddress {
+impl From<[u8; 6]> for MacAddress {
+    fn from(bytes: [u8; 6]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<MacAddress> for [u8; 6] {
+    fn from(mac_address: MacAddress) -> Self {
+        mac_address.0
+    }
+}
+
+impl PartialEq<[u8; 6]> for MacAddress {
+    fn eq(&self, other: &[u8; 6]) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<MacAddress> for [u8; 6] {
+    fn eq(&self, other: &MacAddress) -> bool {
+        *self == other.0
+    }
+}
+
+/// Converts a [`btleplug`] address into a [`MacAddress`].
+///
+/// Both [`BDAddr`](btleplug::api::BDAddr) and [`MacAddress`] store their bytes in display order
+/// (most significant byte first), so both print the same string. Addresses parsed from
+/// advertisements are stored in display order as well, even if the format transmits them in
+/// reverse order.
+#[cfg(feature = "bluetooth")]
+impl From<btleplug::api::BDAddr> for MacAddress {
+    fn from(address: btleplug::api::BDAddr) -> Self {
+        Self(address.into_inner())
+    }
+}
+
+#[cfg(feature = "bluetooth")]
+impl From<MacAddress> for btleplug::api::BDAddr {
+    fn from(mac_address: MacAddress) -> Self {
+        Self::from(mac_address.0)
+    }
+}
+
+impl TryFrom<&[u8]> for MacAddress {
+    type Error = MacAddressParseError;
+
+    /// Creates a MAC address from a slice (which must be exactly 6 bytes long).
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; 6]>::try_from(slice)
+            .map(Self)
+            .map_err(|_| MacAddressParseError)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MacAddress {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl fmt::Display for MacAddress {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
@@ -122,10 +245,44 @@ impl fmt::Debug for MacAddress {
     }
 }
 
+impl fmt::Debug for MacAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Raised when a string is not a valid MAC address.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[error("Invalid MAC address")]
+pub struct MacAddressParseError;
+
+impl FromStr for MacAddress {
+    type Err = MacAddressParseError;
+
+    /// Parses a MAC address in the form `AA:BB:CC:DD:EE:FF` (case-insensitive, `:` or `-` as
+    /// separator).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; 6];
+        let mut parts = s.split([':', '-']);
+        for byte in &mut bytes {
+            let part = parts.next().ok_or(MacAddressParseError)?;
+            if part.len() != 2 || !part.bytes().all(|c| c.is_ascii_hexdigit()) {
+                return Err(MacAddressParseError);
+            }
+            *byte = u8::from_str_radix(part, 16).map_err(|_| MacAddressParseError)?;
+        }
+        if parts.next().is_some() {
+            return Err(MacAddressParseError);
+        }
+        Ok(Self(bytes))
+    }
+}
+
 /// Frame Control Structure
 #[bitfield]
-#[derive(BinRead, Debug)]
+#[derive(BinRead, BinWrite, Clone, Copy, PartialEq, Eq, Debug)]
 #[br(map = Self::from_bytes)]
+#[bw(map = |&x| Self::into_bytes(x))]
 pub struct FrameControl {
     /// Reserved
     #[allow(dead_code)]
@@ -144,29 +301,85 @@ pub struct FrameControl {
     pub registered: bool,
     /// 0: No operation; 1: Request APP to register and bind. It is only effective when the user selects the device to confirm the pairing on the developer platform, otherwise it is set to 0. The original name of this item is bindingCfm, and it has been renamed solicited to "actively request, solicit" APP to register and bind.
     pub solicited: bool,
-    /// 0: Old version certification; 1: Security certification; 2: Standard certification; 3: Reserved
-    pub auth_mode: B2,
+    /// Authentication mode (see [`AuthMode`])
+    pub auth_mode: AuthMode,
     /// Version number (currently v5)
     pub version: B4,
 }
 
+/// Authentication Mode of a MiBeacon device (as indicated by [`FrameControl::auth_mode()`]).
+#[derive(BitfieldSpecifier, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum AuthMode {
+    /// Old version certification
+    OldVersion,
+    /// Security certification
+    Security,
+    /// Standard certification
+    Standard,
+    /// Reserved
+    Reserved,
+}
+
+/// Implements `as_str()` (returning the snake_case name, which is also used when serializing the
+/// enum) and [`fmt::Display`] for an event enum.
+macro_rules! event_labels {
+    ($name:ident { $($variant:pat => $label:literal,)* }) => {
+        impl $name {
+            /// Get the lowercase name of this value.
+            #[must_use]
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($variant => $label,)*
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.as_str())
+            }
+        }
+    };
+}
+
 /// Key ID of a Fingerprint Event
-#[derive(BinRead, Debug)]
-#[br(little)]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[brw(little)]
 pub enum FingerprintEventKeyId {
     /// Lock Administrator (0x00000000)
-    #[br(magic(0x00000000u32))]
+    #[brw(magic(0x00000000u32))]
     LockAdministrator,
     /// Unknown Operator (0xFFFFFFFF)
-    #[br(magic(0xFFFFFFFFu32))]
+    #[brw(magic(0xFFFFFFFFu32))]
     UnknownOperator,
     /// Key ID
     KeyId(u32),
 }
 
+event_labels!(FingerprintEventKeyId {
+    Self::LockAdministrator => "lock_administrator",
+    Self::UnknownOperator => "unknown_operator",
+    Self::KeyId(_) => "key_id",
+});
+
 /// Matching Result of a Fingerprint Event
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[brw(repr(u8))]
 pub enum FingerprintEventMatchingResult {
     /// Matching successful
     MatchingSuccessful = 0x00,
@@ -184,9 +397,19 @@ pub enum FingerprintEventMatchingResult {
     SkinTooWet = 0x06,
 }
 
+event_labels!(FingerprintEventMatchingResult {
+    Self::MatchingSuccessful => "matching_successful",
+    Self::MatchingFailed => "matching_failed",
+    Self::Timeout => "timeout",
+    Self::LowQuality => "low_quality",
+    Self::InsufficientArea => "insufficient_area",
+    Self::SkinTooDry => "skin_too_dry",
+    Self::SkinTooWet => "skin_too_wet",
+});
+
 /// Door Event status
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(repr(u8))]
 pub enum DoorEvent {
     /// Open the door
     DoorOpened = 0x00,
@@ -202,9 +425,23 @@ pub enum DoorEvent {
     DoorStuck = 0x05,
 }
 
+event_labels!(DoorEvent {
+    Self::DoorOpened => "door_opened",
+    Self::DoorClosed => "door_closed",
+    Self::DoorCloseTimeout => "door_close_timeout",
+    Self::KnockingOnTheDoor => "knocking_on_the_door",
+    Self::PryingTheDoorOpen => "prying_the_door_open",
+    Self::DoorStuck => "door_stuck",
+});
+
 /// Arming Event status field.
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[brw(repr(u8))]
 pub enum ArmingEventStatus {
     /// Armed
     Armed = 0x00,
@@ -212,10 +449,15 @@ pub enum ArmingEventStatus {
     Disarmed = 0x01,
 }
 
+event_labels!(ArmingEventStatus {
+    Self::Armed => "armed",
+    Self::Disarmed => "disarmed",
+});
+
 /// Gesture Type of a Gesture Event.
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u16))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(little)]
+#[brw(repr(u16))]
 pub enum Gesture {
     /// Shake
     Shake = 0x0001,
@@ -231,17 +473,27 @@ pub enum Gesture {
     Nudge = 0x0006,
 }
 
+event_labels!(Gesture {
+    Self::Shake => "shake",
+    Self::FlipNinetyDegrees => "flip_ninety_degrees",
+    Self::FlipOneHundredEightyDegrees => "flip_one_hundred_eighty_degrees",
+    Self::PlaneRotation => "plane_rotation",
+    Self::Knock => "knock",
+    Self::Nudge => "nudge",
+});
+
 /// Operation field of a Lock Event.
 #[bitfield]
-#[derive(BinRead, Debug)]
+#[derive(BinRead, BinWrite, Clone, Copy, PartialEq, Eq, Debug)]
 #[br(map = Self::from_bytes)]
+#[bw(map = |&x| Self::into_bytes(x))]
 pub struct LockEventOperation {
     /// The lower 4 bits of the operation field represent the action, which is divided into the following categories:
     ///
     /// - 0000b: Unlock the door from outside
     /// - 0001b: Locked (If it is impossible to distinguish whether the door is locked from inside or outside, this type of report is used)
     /// - 0010b: Enable anti-lock
-    /// - 0011b: Unlock
+    /// - 0011b: Disable anti-lock
     /// - 0100b: Unlock the door from inside
     /// - 0101b: Door locked from inside
     /// - 0110b: Open the child lock
@@ -267,9 +519,204 @@ pub struct LockEventOperation {
     pub operation_method: B4,
 }
 
+impl LockEventOperation {
+    /// Get the typed action of this operation.
+    pub fn action(&self) -> LockAction {
+        match self.operation_action() {
+            0b0000 => LockAction::UnlockedFromOutside,
+            0b0001 => LockAction::Locked,
+            0b0010 => LockAction::AntiLockEnabled,
+            0b0011 => LockAction::AntiLockDisabled,
+            0b0100 => LockAction::UnlockedFromInside,
+            0b0101 => LockAction::LockedFromInside,
+            0b0110 => LockAction::ChildLockEnabled,
+            0b0111 => LockAction::ChildLockDisabled,
+            0b1000 => LockAction::LockedFromOutside,
+            0b1111 => LockAction::Abnormal,
+            value => LockAction::Reserved(value),
+        }
+    }
+
+    /// Get the typed method of this operation.
+    pub fn method(&self) -> LockMethod {
+        match self.operation_method() {
+            0b0000 => LockMethod::Bluetooth,
+            0b0001 => LockMethod::Password,
+            0b0010 => LockMethod::Biometrics,
+            0b0011 => LockMethod::Key,
+            0b0100 => LockMethod::Turntable,
+            0b0101 => LockMethod::Nfc,
+            0b0110 => LockMethod::OneTimePassword,
+            0b0111 => LockMethod::TwoFactorAuthentication,
+            0b1000 => LockMethod::Coercion,
+            0b1001 => LockMethod::Homekit,
+            0b1010 => LockMethod::Manual,
+            0b1011 => LockMethod::Automatic,
+            0b1111 => LockMethod::Abnormal,
+            value => LockMethod::Reserved(value),
+        }
+    }
+}
+
+/// Action of a Lock Event.
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum LockAction {
+    /// Door unlocked from outside
+    UnlockedFromOutside,
+    /// Door locked (inside or outside can't be distinguished)
+    Locked,
+    /// Anti-lock enabled
+    AntiLockEnabled,
+    /// Anti-lock disabled
+    AntiLockDisabled,
+    /// Door unlocked from inside
+    UnlockedFromInside,
+    /// Door locked from inside
+    LockedFromInside,
+    /// Child lock enabled
+    ChildLockEnabled,
+    /// Child lock disabled
+    ChildLockDisabled,
+    /// Door locked from outside
+    LockedFromOutside,
+    /// Abnormal
+    Abnormal,
+    /// Reserved (0b1001 - 0b1110)
+    Reserved(u8),
+}
+
+event_labels!(LockAction {
+    Self::UnlockedFromOutside => "unlocked_from_outside",
+    Self::Locked => "locked",
+    Self::AntiLockEnabled => "anti_lock_enabled",
+    Self::AntiLockDisabled => "anti_lock_disabled",
+    Self::UnlockedFromInside => "unlocked_from_inside",
+    Self::LockedFromInside => "locked_from_inside",
+    Self::ChildLockEnabled => "child_lock_enabled",
+    Self::ChildLockDisabled => "child_lock_disabled",
+    Self::LockedFromOutside => "locked_from_outside",
+    Self::Abnormal => "abnormal",
+    Self::Reserved(_) => "reserved",
+});
+
+/// Method of a Lock Event.
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum LockMethod {
+    /// Bluetooth
+    Bluetooth,
+    /// Password
+    Password,
+    /// Biometrics (fingerprints, faces, human veins, palm prints, etc.)
+    Biometrics,
+    /// Key
+    Key,
+    /// Turntable
+    Turntable,
+    /// NFC
+    Nfc,
+    /// One-time password
+    OneTimePassword,
+    /// Two-factor authentication
+    TwoFactorAuthentication,
+    /// Coercion
+    Coercion,
+    /// Homekit
+    Homekit,
+    /// Manual ("Artificial")
+    Manual,
+    /// Automatic
+    Automatic,
+    /// Abnormal
+    Abnormal,
+    /// Reserved (0b1100 - 0b1110)
+    Reserved(u8),
+}
+
+event_labels!(LockMethod {
+    Self::Bluetooth => "bluetooth",
+    Self::Password => "password",
+    Self::Biometrics => "biometrics",
+    Self::Key => "key",
+    Self::Turntable => "turntable",
+    Self::Nfc => "nfc",
+    Self::OneTimePassword => "one_time_password",
+    Self::TwoFactorAuthentication => "two_factor_authentication",
+    Self::Coercion => "coercion",
+    Self::Homekit => "homekit",
+    Self::Manual => "manual",
+    Self::Automatic => "automatic",
+    Self::Abnormal => "abnormal",
+    Self::Reserved(_) => "reserved",
+});
+
+/// Category of the Key ID of a Lock Event.
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum LockKeyCategory {
+    /// Lock administrator (0x00000000)
+    LockAdministrator,
+    /// Unknown operator (0xFFFFFFFF)
+    UnknownOperator,
+    /// Invalid operator (0xDEADBEEF)
+    InvalidOperator,
+    /// Bluetooth (0x00000001 - 0x7FFFFFFF)
+    Bluetooth(u32),
+    /// Biometrics - Fingerprint (0x80010000 - 0x8001FFFF)
+    Fingerprint(u16),
+    /// Password (0x80020000 - 0x8002FFFF)
+    Password(u16),
+    /// Keys (0x80030000 - 0x8003FFFF)
+    Key(u16),
+    /// NFC (0x80040000 - 0x8004FFFF)
+    Nfc(u16),
+    /// Two-factor authentication (0x80050000 - 0x8005FFFF)
+    TwoFactorAuthentication(u16),
+    /// Biometrics - Face (0x80060000 - 0x8006FFFF)
+    Face(u16),
+    /// Biometrics - Finger vein (0x80070000 - 0x8007FFFF)
+    FingerVein(u16),
+    /// Biometrics - Palmprint (0x80080000 - 0x8008FFFF)
+    Palmprint(u16),
+    /// Exception (0xC0DE0000 - 0xC0DEFFFF), see [`LockEvent::key_id`] for details.
+    Exception(u16),
+    /// Unknown Key ID
+    Unknown(u32),
+}
+
+event_labels!(LockKeyCategory {
+    Self::LockAdministrator => "lock_administrator",
+    Self::UnknownOperator => "unknown_operator",
+    Self::InvalidOperator => "invalid_operator",
+    Self::Bluetooth(_) => "bluetooth",
+    Self::Fingerprint(_) => "fingerprint",
+    Self::Password(_) => "password",
+    Self::Key(_) => "key",
+    Self::Nfc(_) => "nfc",
+    Self::TwoFactorAuthentication(_) => "two_factor_authentication",
+    Self::Face(_) => "face",
+    Self::FingerVein(_) => "finger_vein",
+    Self::Palmprint(_) => "palmprint",
+    Self::Exception(_) => "exception",
+    Self::Unknown(_) => "unknown",
+});
+
 /// Lock Event Payload
-#[derive(BinRead, Debug)]
-#[br(little)]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(little)]
 pub struct LockEvent {
     /// Lock Event Operation.
     pub operation: LockEventOperation,
@@ -321,10 +768,45 @@ pub struct LockEvent {
     pub timestamp: u32,
 }
 
+/// Converts a MiBeacon timestamp (seconds since the UNIX epoch, UTC) to a [`chrono::DateTime`].
+#[cfg(feature = "chrono")]
+pub fn timestamp_utc(timestamp: u32) -> chrono::DateTime<chrono::Utc> {
+    chrono::DateTime::from_timestamp(i64::from(timestamp), 0)
+        .expect("u32 timestamps are always in range")
+}
+
+impl LockEvent {
+    /// Get the [`timestamp`](Self::timestamp) as [`chrono::DateTime`].
+    #[cfg(feature = "chrono")]
+    pub fn timestamp_utc(&self) -> chrono::DateTime<chrono::Utc> {
+        timestamp_utc(self.timestamp)
+    }
+
+    /// Classify the [`key_id`](Self::key_id) according to the documented ranges.
+    pub fn key_category(&self) -> LockKeyCategory {
+        match self.key_id {
+            0x0000_0000 => LockKeyCategory::LockAdministrator,
+            0xFFFF_FFFF => LockKeyCategory::UnknownOperator,
+            0xDEAD_BEEF => LockKeyCategory::InvalidOperator,
+            id @ 0x0000_0001..=0x7FFF_FFFF => LockKeyCategory::Bluetooth(id),
+            id @ 0x8001_0000..=0x8001_FFFF => LockKeyCategory::Fingerprint(id as u16),
+            id @ 0x8002_0000..=0x8002_FFFF => LockKeyCategory::Password(id as u16),
+            id @ 0x8003_0000..=0x8003_FFFF => LockKeyCategory::Key(id as u16),
+            id @ 0x8004_0000..=0x8004_FFFF => LockKeyCategory::Nfc(id as u16),
+            id @ 0x8005_0000..=0x8005_FFFF => LockKeyCategory::TwoFactorAuthentication(id as u16),
+            id @ 0x8006_0000..=0x8006_FFFF => LockKeyCategory::Face(id as u16),
+            id @ 0x8007_0000..=0x8007_FFFF => LockKeyCategory::FingerVein(id as u16),
+            id @ 0x8008_0000..=0x8008_FFFF => LockKeyCategory::Palmprint(id as u16),
+            id @ 0xC0DE_0000..=0xC0DE_FFFF => LockKeyCategory::Exception(id as u16),
+            id => LockKeyCategory::Unknown(id),
+        }
+    }
+}
+
 /// Flooding Alarm Event Payload
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(little)]
+#[brw(repr(u8))]
 pub enum FloodingAlarmEvent {
     /// Alarm Cleared
     AlarmCleared = 0x00,
@@ -332,10 +814,15 @@ pub enum FloodingAlarmEvent {
     Alarm = 0x01,
 }
 
+event_labels!(FloodingAlarmEvent {
+    Self::AlarmCleared => "alarm_cleared",
+    Self::Alarm => "alarm",
+});
+
 /// Smoke Alarm Event Payload
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(little)]
+#[brw(repr(u8))]
 pub enum SmokeAlarmEvent {
     /// Normal Monitoring
     Normal = 0x00,
@@ -349,10 +836,18 @@ pub enum SmokeAlarmEvent {
     AnalogAlarm = 0x04,
 }
 
+event_labels!(SmokeAlarmEvent {
+    Self::Normal => "normal",
+    Self::FireAlarm => "fire_alarm",
+    Self::EquipmentFailure => "equipment_failure",
+    Self::EquipmentSelfTest => "equipment_self_test",
+    Self::AnalogAlarm => "analog_alarm",
+});
+
 /// Gas Alarm Event Payload
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(little)]
+#[brw(repr(u8))]
 pub enum GasAlarmEvent {
     /// Normal Monitoring
     Normal = 0x00,
@@ -370,10 +865,25 @@ pub enum GasAlarmEvent {
     AnalogAlarm = 0x06,
 }
 
+event_labels!(GasAlarmEvent {
+    Self::Normal => "normal",
+    Self::GasLeakAlarm => "gas_leak_alarm",
+    Self::EquipmentFailure => "equipment_failure",
+    Self::SensorLifeExpiration => "sensor_life_expiration",
+    Self::SensorPreheating => "sensor_preheating",
+    Self::EquipmentSelfTest => "equipment_self_test",
+    Self::AnalogAlarm => "analog_alarm",
+});
+
 /// Toothbrush Event Type
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[brw(little)]
+#[brw(repr(u8))]
 pub enum ToothbrushEventType {
     /// Brushing Started
     BrushingStarted,
@@ -381,10 +891,20 @@ pub enum ToothbrushEventType {
     BrushingEnded,
 }
 
+event_labels!(ToothbrushEventType {
+    Self::BrushingStarted => "brushing_started",
+    Self::BrushingEnded => "brushing_ended",
+});
+
 /// Maoyan Doorbell Camera Event Payload
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[brw(little)]
+#[brw(repr(u8))]
 pub enum DoorbellCameraEvent {
     /// Someone is staying
     SomeoneIsStaying = 0x00,
@@ -402,10 +922,20 @@ pub enum DoorbellCameraEvent {
     AbnormalUnlocking = 0x06,
 }
 
+event_labels!(DoorbellCameraEvent {
+    Self::SomeoneIsStaying => "someone_is_staying",
+    Self::SomeoneIsPassingBy => "someone_is_passing_by",
+    Self::SomeoneIsRingingTheBell => "someone_is_ringing_the_bell",
+    Self::SomeoneIsLeavingAMessage => "someone_is_leaving_a_message",
+    Self::EquipmentDamage => "equipment_damage",
+    Self::DuressAlarm => "duress_alarm",
+    Self::AbnormalUnlocking => "abnormal_unlocking",
+});
+
 /// Weighing Event Type
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(little)]
+#[brw(repr(u8))]
 pub enum WeighingEventType {
     /// Current Weight
     CurrentWeight = 0x00,
@@ -415,10 +945,21 @@ pub enum WeighingEventType {
     IncreasedWeight = 0x02,
 }
 
+event_labels!(WeighingEventType {
+    Self::CurrentWeight => "current_weight",
+    Self::ReducedWeight => "reduced_weight",
+    Self::IncreasedWeight => "increased_weight",
+});
+
 /// Button Event Type
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[brw(little)]
+#[brw(repr(u8))]
 pub enum ButtonEventType {
     /// Single Click
     SingleClick = 0x00,
@@ -430,10 +971,170 @@ pub enum ButtonEventType {
     TripleClick = 0x03,
 }
 
+event_labels!(ButtonEventType {
+    Self::SingleClick => "single_click",
+    Self::DoubleClick => "double_click",
+    Self::LongPress => "long_press",
+    Self::TripleClick => "triple_click",
+});
+
+/// Action performed on the knob of a dimmer (e.g. YLKG07YL/YLKG08YL).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum DimmerAction {
+    /// Knob was pressed (the value is the number of presses)
+    ShortPress,
+    /// Knob was pressed and held (the value is the duration in seconds)
+    LongPress,
+    /// Knob was rotated counter-clockwise (the value is the number of steps)
+    RotateLeft,
+    /// Knob was rotated clockwise (the value is the number of steps)
+    RotateRight,
+    /// Knob was rotated counter-clockwise while pressed (the value is the number of steps)
+    RotateLeftPressed,
+    /// Knob was rotated clockwise while pressed (the value is the number of steps)
+    RotateRightPressed,
+}
+
+event_labels!(DimmerAction {
+    Self::ShortPress => "short_press",
+    Self::LongPress => "long_press",
+    Self::RotateLeft => "rotate_left",
+    Self::RotateRight => "rotate_right",
+    Self::RotateLeftPressed => "rotate_left_pressed",
+    Self::RotateRightPressed => "rotate_right_pressed",
+});
+
+/// State of a smart kettle (e.g. YM-K1501), derived from the
+/// [`MiBeaconObjectPayload::PowerAndTemperature`] object.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum KettleState {
+    /// Kettle is switched off
+    Off,
+    /// Kettle is heating the water
+    Heating,
+    /// Kettle is heating and the water has reached the boiling point (see
+    /// [`KettleState::BOILED_TEMPERATURE`])
+    Boiled,
+}
+
+event_labels!(KettleState {
+    Self::Off => "off",
+    Self::Heating => "heating",
+    Self::Boiled => "boiled",
+});
+
+impl KettleState {
+    /// Temperature (°C) at which the water is considered boiled.
+    ///
+    /// This is slightly below 100 °C, because water boils at lower temperatures at higher
+    /// altitudes and the kettle stops heating before reaching 100 °C.
+    pub const BOILED_TEMPERATURE: u8 = 95;
+
+    /// Classify the kettle state from the power byte (0 = off, 1 = on) and the water temperature
+    /// (°C).
+    ///
+    /// The power byte is the on/off switch state, as decoded by `obj1005` in bleparser
+    /// (`ble_monitor/ble_parser/xiaomi.py`) and xiaomi-ble (`xiaomi_ble/parser.py`). Neither
+    /// defines any other values, so these are not classified.
+    #[must_use]
+    pub fn from_power_and_temperature(power: u8, temperature: u8) -> Option<Self> {
+        match power {
+            0 => Some(Self::Off),
+            1 if temperature >= Self::BOILED_TEMPERATURE => Some(Self::Boiled),
+            1 => Some(Self::Heating),
+            _ => None,
+        }
+    }
+}
+
+/// Button of a remote control (YLYK01YL and its variants).
+///
+/// The variants share the same key indices, but their buttons are labeled differently.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum RemoteButton {
+    // YLYK01YL
+    /// On
+    On,
+    /// Off
+    Off,
+    /// Sun (Brightness mode)
+    Sun,
+    /// Plus (`+`)
+    Plus,
+    /// Mode (`M`)
+    Mode,
+    /// Minus (`-`)
+    Minus,
+
+    // YLYK01YL-FANCL
+    /// Fan on/off
+    FanToggle,
+    /// Light on/off
+    LightToggle,
+    /// Wind Speed
+    WindSpeed,
+    /// Color Temperature
+    ColorTemperature,
+    /// Wind Mode
+    WindMode,
+    /// Brightness
+    Brightness,
+
+    // YLYK01YL-VENFAN
+    /// Swing
+    Swing,
+    /// Power on/off
+    PowerToggle,
+    /// Timer (60 minutes)
+    Timer60Minutes,
+    /// Strong Wind Speed
+    StrongWindSpeed,
+    /// Timer (30 minutes)
+    Timer30Minutes,
+    /// Low Wind Speed
+    LowWindSpeed,
+}
+
+event_labels!(RemoteButton {
+    Self::On => "on",
+    Self::Off => "off",
+    Self::Sun => "sun",
+    Self::Plus => "plus",
+    Self::Mode => "mode",
+    Self::Minus => "minus",
+    Self::FanToggle => "fan_toggle",
+    Self::LightToggle => "light_toggle",
+    Self::WindSpeed => "wind_speed",
+    Self::ColorTemperature => "color_temperature",
+    Self::WindMode => "wind_mode",
+    Self::Brightness => "brightness",
+    Self::Swing => "swing",
+    Self::PowerToggle => "power_toggle",
+    Self::Timer60Minutes => "timer60_minutes",
+    Self::StrongWindSpeed => "strong_wind_speed",
+    Self::Timer30Minutes => "timer30_minutes",
+    Self::LowWindSpeed => "low_wind_speed",
+});
+
 /// Sleep State
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(little)]
+#[brw(repr(u8))]
 pub enum SleepState {
     /// Not Sleeping
     NotSleeping = 0x00,
@@ -450,8 +1151,9 @@ pub enum SleepState {
 /// - 0x06: Anti-lock + Lock tongue pops up (dead tongue, oblique tongue pops up)
 /// - 0x07: All bolts pop out (square bolt, dead bolt, and oblique bolt pop out)
 #[bitfield]
-#[derive(BinRead, Debug)]
+#[derive(BinRead, BinWrite, Clone, Copy, PartialEq, Eq, Debug)]
 #[br(map = Self::from_bytes)]
+#[bw(map = |&x| Self::into_bytes(x))]
 pub struct LockState {
     /// Tongue ejected or retracted
     pub tongue_ejected: bool,
@@ -466,10 +1168,19 @@ pub struct LockState {
     reserved: B4,
 }
 
+impl LockState {
+    /// Returns `true` if any of the lock tongues is ejected.
+    ///
+    /// This corresponds to all documented "normal combinations" except 0x00 (unlocked).
+    pub fn is_locked(&self) -> bool {
+        self.tongue_ejected() || self.dead_tongue_ejected() || self.latch_ejected()
+    }
+}
+
 /// Door State
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(little)]
+#[brw(repr(u8))]
 pub enum DoorState {
     /// Door is open.
     Open = 0x00,
@@ -480,9 +1191,9 @@ pub enum DoorState {
 }
 
 /// Binding State
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(little)]
+#[brw(repr(u8))]
 pub enum BindingState {
     /// Unbound
     Unbound = 0x00,
@@ -491,9 +1202,9 @@ pub enum BindingState {
 }
 
 /// Switch State
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(little)]
+#[brw(repr(u8))]
 pub enum SwitchState {
     /// Disabled
     Disabled = 0x00,
@@ -502,9 +1213,9 @@ pub enum SwitchState {
 }
 
 /// Water Immersion State
-#[derive(BinRead, Debug)]
-#[br(little)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(little)]
+#[brw(repr(u8))]
 pub enum WaterImmersionState {
     /// Not Submerged
     NotSubmerged = 0x00,
@@ -513,8 +1224,8 @@ pub enum WaterImmersionState {
 }
 
 /// Smoke Detection State
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(repr(u8))]
 pub enum SmokeDetectionState {
     /// Normal monitoring
     Normal = 0x00,
@@ -525,8 +1236,8 @@ pub enum SmokeDetectionState {
 }
 
 /// Gas Leakage Detection State
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(repr(u8))]
 pub enum GasLeakageDetectionState {
     /// Leakage
     Leakage = 0x00,
@@ -535,8 +1246,8 @@ pub enum GasLeakageDetectionState {
 }
 
 /// Light Intensity State
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(repr(u8))]
 pub enum LightIntensityState {
     /// Weak Light
     Dark = 0x00,
@@ -545,8 +1256,8 @@ pub enum LightIntensityState {
 }
 
 /// Door Sensor State
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(repr(u8))]
 pub enum DoorSensorState {
     /// Door Open
     DoorOpen = 0x00,
@@ -559,8 +1270,8 @@ pub enum DoorSensorState {
 }
 
 /// Movement Detection State
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(repr(u8))]
 pub enum MovementDetectionState {
     /// Movement detected in configured timeframe
     MovementDetectedWithinTimeframe = 0x00,
@@ -569,21 +1280,21 @@ pub enum MovementDetectionState {
 }
 
 /// Smart Pillow State
-#[derive(BinRead, Debug)]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
 pub enum SmartPillowState {
     /// Out of Bed
-    #[br(magic(0x00u8))]
+    #[brw(magic(0x00u8))]
     OutOfBed,
     /// In Bed
-    #[br(magic(0x01u8))]
+    #[brw(magic(0x01u8))]
     InBed,
     /// Reserved (0x02 - 0xFF)
     Reserved(u8),
 }
 
 /// Huami Mi Band Sleep State
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(repr(u8))]
 pub enum MiBandSleepState {
     /// No Sleep Event occurred
     None = 0x00,
@@ -594,8 +1305,8 @@ pub enum MiBandSleepState {
 }
 
 /// Roidmi Vacuum Cleaner State
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(repr(u8))]
 pub enum RoidmiVacuumCleanerState {
     /// Vacuum Cleaner is charging
     Charging = 0x00,
@@ -610,8 +1321,8 @@ pub enum RoidmiVacuumCleanerState {
 }
 
 /// Flower and Grass Detector Event
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[brw(repr(u8))]
 pub enum FlowerAndGrassDetectorEvent {
     /// Normal
     Normal = 0x00,
@@ -619,9 +1330,19 @@ pub enum FlowerAndGrassDetectorEvent {
     Unplugged = 0x01,
 }
 
+event_labels!(FlowerAndGrassDetectorEvent {
+    Self::Normal => "normal",
+    Self::Unplugged => "unplugged",
+});
+
 /// Quingping Sensor Location Event
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[brw(repr(u8))]
 pub enum QuingpingSensorLocationEvent {
     /// Separated From Base
     SeparatedFromBase = 0x00,
@@ -629,9 +1350,19 @@ pub enum QuingpingSensorLocationEvent {
     Connected = 0x01,
 }
 
+event_labels!(QuingpingSensorLocationEvent {
+    Self::SeparatedFromBase => "separated_from_base",
+    Self::Connected => "connected",
+});
+
 /// Quingping Pomodoro Event
-#[derive(BinRead, Debug)]
-#[br(repr(u8))]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[brw(repr(u8))]
 pub enum QuingpingPomodoroEvent {
     /// Start of Pomodoro
     Start = 0x00,
@@ -643,8 +1374,15 @@ pub enum QuingpingPomodoroEvent {
     EndOfBreak = 0x03,
 }
 
+event_labels!(QuingpingPomodoroEvent {
+    Self::Start => "start",
+    Self::End => "end",
+    Self::StartOfBreak => "start_of_break",
+    Self::EndOfBreak => "end_of_break",
+});
+
 /// Parsed payload of a MiBeacon object.
-#[derive(BinRead, Debug)]
+#[derive(BinRead, BinWrite, Clone, PartialEq, Eq, Debug)]
 #[br(import(id: u16, length: u8))]
 pub enum MiBeaconObjectPayload {
     // Common Events (0x0000 - 0x1001)
@@ -794,7 +1532,7 @@ pub enum MiBeaconObjectPayload {
         ///
         /// - **Range:** 0-100
         #[br(if(length == 2))]
-        scope: Option<u8>,
+        score: Option<u8>,
     },
 
     /// Maoyan Doorbell Camera Event
@@ -831,6 +1569,23 @@ pub enum MiBeaconObjectPayload {
         event_type: ButtonEventType,
     },
 
+    /// Key Event
+    ///
+    /// The meaning of the fields depends on the device (e.g. remote controls or dimmers).
+    ///
+    /// - **Time Interval:** 0
+    /// - **Change:** 0
+    #[br(pre_assert(id == 0x1001))]
+    #[br(assert(length == 3))]
+    KeyEvent {
+        /// Index of the key (or, for dimmers, the rotation while pressed)
+        key: u8,
+        /// Device-specific value (e.g. the number of rotation steps of a dimmer)
+        value: u8,
+        /// Device-specific event type (e.g. single press or rotation)
+        event_type: u8,
+    },
+
     // Common Attributes (0x1002 - 0x1FFF)
     /// Sleep (on/off)
     ///
@@ -857,22 +1612,28 @@ pub enum MiBeaconObjectPayload {
     Temperature(i16),
 
     /// Power (on/off) and Temperature (°C)
+    ///
+    /// Sent by smart kettles (see [`KettleState`]).
     #[br(pre_assert(id == 0x1005))]
     #[br(assert(length == 2))]
     PowerAndTemperature {
-        /// Power (on/off)
+        /// Power (0 = off, 1 = on)
         power: u8,
         /// Temperature (°C)
         temperature: u8,
     },
 
-    /// Humidity (‰)
+    /// Humidity (‰, i.e. tenths of a percent)
     ///
     /// - **Time Interval:** 600 s
     /// - **Change:** 1
     /// - **Range:** 0-1000
+    ///
+    /// Values outside of the range are invalid (the object is parsed as
+    /// [`MiBeaconObjectPayload::Unknown`] instead).
     #[br(pre_assert(id == 0x1006))]
     #[br(assert(length == 2))]
+    #[br(assert(self_0 <= 1000))]
     Humidity(u16),
 
     /// Illuminance (lx)
@@ -880,8 +1641,12 @@ pub enum MiBeaconObjectPayload {
     /// - **Time Interval:** 600 s
     /// - **Change:** 1
     /// - **Range:** 0-120000
+    ///
+    /// Values outside of the range are invalid (the object is parsed as
+    /// [`MiBeaconObjectPayload::Unknown`] instead).
     #[br(pre_assert(id == 0x1007))]
     #[br(assert(length == 3))]
+    #[br(assert(self_0.as_u32() <= 120_000))]
     Illuminance(U24),
 
     /// Soil Moisture (%)
@@ -911,6 +1676,22 @@ pub enum MiBeaconObjectPayload {
     #[br(assert(length == 1))]
     BatteryPower(u8),
 
+    /// Temperature (degrees decicelsius) and Humidity (‰)
+    ///
+    /// - **Time Interval:** 600 s
+    /// - **Change:** 1
+    ///
+    /// As for [`MiBeaconObjectPayload::Humidity`], humidity values above 1000 are invalid.
+    #[br(pre_assert(id == 0x100D))]
+    #[br(assert(length == 4))]
+    #[br(assert(humidity <= 1000))]
+    TemperatureAndHumidity {
+        /// Temperature (degrees decicelsius)
+        temperature: i16,
+        /// Humidity (‰)
+        humidity: u16,
+    },
+
     /// Lock Sensor
     ///
     /// - **Time Interval:** 60 s
@@ -951,13 +1732,17 @@ pub enum MiBeaconObjectPayload {
     #[br(assert(length == 1))]
     Switch(SwitchState),
 
-    /// Remaining Consumable Supplies (%)
+    /// Remaining Consumable Supplies (%), e.g. the refill level of the WX08ZM mosquito repellent.
     ///
     /// - **Time Interval:** 600 s
     /// - **Change:** 0
     /// - **Range:** 0-100
+    ///
+    /// Values outside of the range are invalid (the object is parsed as
+    /// [`MiBeaconObjectPayload::Unknown`] instead).
     #[br(pre_assert(id == 0x1013))]
     #[br(assert(length == 1))]
+    #[br(assert(self_0 <= 100))]
     RemainingSupplies(u8),
 
     /// Water Immersion (yes/no)
@@ -1010,6 +1795,8 @@ pub enum MiBeaconObjectPayload {
 
     /// Weight (g)
     ///
+    /// Like all weight measurements, the sensor event is converted to kilograms.
+    ///
     /// - **Time Interval:** 600 s
     /// - **Change:** 0
     #[br(pre_assert(id == 0x101A))]
@@ -1140,30 +1927,345 @@ pub enum MiBeaconObjectPayload {
         score: Option<u8>,
     },
 
+    // MiOT Spec Attributes (0x4800 - 0x4FFF)
+    /// Battery Level (%), e.g. sent by door locks
+    ///
+    /// See `obj4803` in the [xiaomi-ble](https://github.com/Bluetooth-Devices/xiaomi-ble/blob/main/src/xiaomi_ble/parser.py)
+    /// parser.
+    ///
+    /// - **Range:** 0-100
+    ///
+    /// Values outside of the range are invalid (the object is parsed as
+    /// [`MiBeaconObjectPayload::Unknown`] instead).
+    #[br(pre_assert(id == 0x4803))]
+    #[br(assert(length == 1))]
+    #[br(assert(self_0 <= 100))]
+    BatteryLevel(u8),
+
     /// Unknown Payload
     Unknown(#[br(count = usize::from(length))] Vec<u8>),
 }
 
+/// Device ID of the YLKG07YL/YLKG08YL dimmer.
+const DIMMER_DEVICE_ID: u16 = 0x03B6;
+/// Device ID of the YLYK01YL remote control.
+const REMOTE_DEVICE_ID: u16 = 0x0153;
+/// Device ID of the YLYK01YL-FANCL fan remote control.
+const FAN_REMOTE_DEVICE_ID: u16 = 0x068E;
+/// Device ID of the YLYK01YL-VENFAN ventilator fan remote control.
+const VENTILATOR_FAN_REMOTE_DEVICE_ID: u16 = 0x04E6;
+
+/// Map a key event to [`SensorEvent`]s, depending on the device that sent it.
+///
+/// The encoding follows the reverse engineering efforts of
+/// [bleparser](https://github.com/Ernst79/bleparser/blob/master/package/bleparser/xiaomi.py).
+fn key_events(device_id: u16, key: u8, value: u8, event_type: u8) -> ObjectSensorEvents {
+    let event = match device_id {
+        DIMMER_DEVICE_ID => dimmer_action(key, value, event_type)
+            .map(|(action, value)| DiscreteEvent::Dimmer { action, value }),
+        _ => remote_button(device_id, key)
+            .zip(remote_button_event_type(event_type))
+            .map(|(button, event_type)| DiscreteEvent::RemoteButton { button, event_type }),
+    };
+    match event {
+        Some(event) => object_sensor_events![SensorEvent::DiscreteEvent(event)],
+        None => {
+            log_warn!(
+                "Ignoring unhandled key event (device 0x{:04X}, key {}, value {}, event type {})",
+                device_id,
+                key,
+                value,
+                event_type
+            );
+            object_sensor_events![]
+        }
+    }
+}
+
+/// Decode the key event of a dimmer into the performed action and its value.
+fn dimmer_action(key: u8, value: u8, event_type: u8) -> Option<(DimmerAction, u8)> {
+    match event_type {
+        3 => match key {
+            0 => Some((DimmerAction::ShortPress, value)),
+            1 => Some((DimmerAction::LongPress, value)),
+            _ => None,
+        },
+        // Rotation is a signed number of steps, either in the value (if the knob is not pressed)
+        // or in the key (if the knob is pressed).
+        4 => match (key, value) {
+            (0, 0..=127) => Some((DimmerAction::RotateRight, value)),
+            (0, _) => Some((DimmerAction::RotateLeft, value.wrapping_neg())),
+            (1..=127, _) => Some((DimmerAction::RotateRightPressed, key)),
+            (_, _) => Some((DimmerAction::RotateLeftPressed, key.wrapping_neg())),
+        },
+        _ => None,
+    }
+}
+
+/// Get the label of the pressed button of a remote control (the buttons of the YLYK01YL variants
+/// share the same key indices).
+fn remote_button(device_id: u16, key: u8) -> Option<RemoteButton> {
+    let buttons = match device_id {
+        REMOTE_DEVICE_ID => [
+            RemoteButton::On,
+            RemoteButton::Off,
+            RemoteButton::Sun,
+            RemoteButton::Plus,
+            RemoteButton::Mode,
+            RemoteButton::Minus,
+        ],
+        FAN_REMOTE_DEVICE_ID => [
+            RemoteButton::FanToggle,
+            RemoteButton::LightToggle,
+            RemoteButton::WindSpeed,
+            RemoteButton::ColorTemperature,
+            RemoteButton::WindMode,
+            RemoteButton::Brightness,
+        ],
+        VENTILATOR_FAN_REMOTE_DEVICE_ID => [
+            RemoteButton::Swing,
+            RemoteButton::PowerToggle,
+            RemoteButton::Timer60Minutes,
+            RemoteButton::StrongWindSpeed,
+            RemoteButton::Timer30Minutes,
+            RemoteButton::LowWindSpeed,
+        ],
+        _ => return None,
+    };
+    buttons.get(usize::from(key)).copied()
+}
+
+/// Get the type of a button press of a remote control.
+fn remote_button_event_type(event_type: u8) -> Option<ButtonEventType> {
+    match event_type {
+        0 => Some(ButtonEventType::SingleClick),
+        1 => Some(ButtonEventType::DoubleClick),
+        2 => Some(ButtonEventType::LongPress),
+        _ => None,
+    }
+}
+
+/// Map a toothbrush event (with optional timestamp and score) to [`SensorEvent`]s.
+fn toothbrush_events(
+    event_type: ToothbrushEventType,
+    timestamp: Option<u32>,
+    score: Option<u8>,
+) -> ObjectSensorEvents {
+    let event = SensorEvent::DiscreteEvent(DiscreteEvent::Toothbrush {
+        event_type,
+        timestamp,
+    });
+    match score {
+        Some(score) => object_sensor_events![
+            event,
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::ToothbrushScore,
+                value: f64::from(score),
+                unit: UnitOfMeasurement::Unitless,
+            }
+        ],
+        None => object_sensor_events![event],
+    }
+}
+
+/// Iterator over the [`SensorEvent`]s of a single MiBeacon object (see
+/// [`MiBeaconObjectPayload::iter_sensor_events`]).
+///
+/// Objects yield at most [`ObjectSensorEvents::CAPACITY`] events, so these are stored inline
+/// instead of allocating a [`Vec`] for every object.
+#[derive(Clone, Debug)]
+pub struct ObjectSensorEvents {
+    inner: core::iter::Flatten<core::array::IntoIter<Option<SensorEvent>, MAX_OBJECT_EVENTS>>,
+}
+
+/// Maximum number of [`SensorEvent`]s per MiBeacon object (i.e. for the lock state object).
+const MAX_OBJECT_EVENTS: usize = 3;
+
+impl ObjectSensorEvents {
+    /// Maximum number of events per object.
+    pub const CAPACITY: usize = MAX_OBJECT_EVENTS;
+
+    fn from_array<const N: usize>(events: [SensorEvent; N]) -> Self {
+        debug_assert!(N <= Self::CAPACITY);
+        let mut slots = [None; Self::CAPACITY];
+        for (slot, event) in slots.iter_mut().zip(events) {
+            *slot = Some(event);
+        }
+        Self {
+            inner: slots.into_iter().flatten(),
+        }
+    }
+}
+
+impl Iterator for ObjectSensorEvents {
+    type Item = SensorEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Creates an [`ObjectSensorEvents`] iterator (with the same syntax as [`vec!`]).
+macro_rules! object_sensor_events {
+    ($($event:expr),* $(,)?) => {
+        ObjectSensorEvents::from_array([$($event),*])
+    };
+}
+use object_sensor_events;
+
+/// Information about the advertisement that an object was sent in, which is needed to interpret
+/// some objects correctly (see [`MiBeaconObjectPayload::to_sensor_events`]).
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct ParseContext {
+    /// Xiaomi Device ID
+    pub device_id: u16,
+    /// Packet ID
+    pub packet_id: u8,
+    /// MAC Address (if included in the advertisement)
+    pub mac: Option<MacAddress>,
+}
+
+impl From<&MiBeaconServiceAdvertisement> for ParseContext {
+    fn from(advertisement: &MiBeaconServiceAdvertisement) -> Self {
+        Self {
+            device_id: advertisement.device_id,
+            packet_id: advertisement.packet_id,
+            mac: advertisement.mac_address,
+        }
+    }
+}
+
+/// Reason why an object did not yield any [`SensorEvent`]s (see [`UnhandledObject`]).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum UnhandledReason {
+    /// The object ID is not decoded by this crate (see [`MiBeaconObjectPayload::is_known_id`]).
+    UnknownId,
+    /// The object was decoded, but its value cannot be mapped to a sensor event (e.g. an RSSI
+    /// object or a key event of an unsupported device).
+    UnsupportedState,
+    /// The object ID is supported, but the object has an unexpected length or its value is out of
+    /// the valid range.
+    OutOfRange,
+}
+
+/// An object that did not yield any [`SensorEvent`]s (see
+/// [`MiBeaconServiceAdvertisement::try_iter_sensor_events`]).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct UnhandledObject {
+    /// Object ID
+    pub id: u16,
+    /// Reason why the object was not handled
+    pub reason: UnhandledReason,
+}
+
+impl UnhandledObject {
+    /// Determine why the object was not handled.
+    fn new(object: &MiBeaconObject) -> Self {
+        let reason = if !object.payload.is_unknown() {
+            UnhandledReason::UnsupportedState
+        } else if MiBeaconObjectPayload::is_handled_id(object.id) {
+            UnhandledReason::OutOfRange
+        } else {
+            UnhandledReason::UnknownId
+        };
+        Self {
+            id: object.id,
+            reason,
+        }
+    }
+}
+
 /// MiBeacon Object
-#[binread]
-#[br(little)]
-#[derive(Debug)]
+///
+/// Objects can be constructed with [`MiBeaconObject::new`] (e.g. to write them back to bytes).
+#[binrw]
+#[brw(little)]
+#[derive(Clone, Debug)]
 pub struct MiBeaconObject {
     /// Object ID
     pub id: u16,
     /// Object Length
     pub length: u8,
+    /// Raw Object Payload (only stored if the `raw-payload` feature is enabled, empty otherwise)
+    #[br(restore_position, count = if cfg!(feature = "raw-payload") { usize::from(length) } else { 0 })]
+    #[bw(ignore)]
+    raw_payload: Vec<u8>,
     /// Object Payload
     #[br(args(id, length))]
     pub payload: MiBeaconObjectPayload,
 }
 
+impl MiBeaconObject {
+    /// Creates an object with the given ID and payload (the object length is calculated from the
+    /// payload).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload is longer than 255 bytes.
+    #[must_use]
+    pub fn new(id: u16, payload: MiBeaconObjectPayload) -> Self {
+        let mut writer = Cursor::new(Vec::new());
+        payload
+            .write_le(&mut writer)
+            .expect("writing to a Vec should not fail");
+        let raw_payload = writer.into_inner();
+        let length = u8::try_from(raw_payload.len()).expect("object payload is too long");
+        Self {
+            id,
+            length,
+            raw_payload: if cfg!(feature = "raw-payload") {
+                raw_payload
+            } else {
+                Vec::new()
+            },
+            payload,
+        }
+    }
+
+    /// Parses a sequence of concatenated objects (i.e. the object region of a MiBeacon
+    /// advertisement, without the preceding header fields).
+    ///
+    /// The declared length of each object is checked against the remaining bytes, so a
+    /// truncated object results in [`ParseError::ObjectLengthExceedsBuffer`]. Like for whole
+    /// advertisements, at most [`MiBeaconServiceAdvertisement::MAX_OBJECTS`] objects are parsed.
+    pub fn parse_all(bytes: &[u8]) -> Result<Vec<Self>, ParseError> {
+        parse_objects(&mut Cursor::new(bytes), binrw::Endian::Little, (false,))
+            .map_err(|err| ParseError::from_binrw(err, bytes.len(), None))
+    }
+
+    /// Get the raw bytes of the object payload.
+    ///
+    /// Without the `raw-payload` feature, the bytes are only available for
+    /// [`MiBeaconObjectPayload::Unknown`] payloads and [`None`] is returned otherwise.
+    #[must_use]
+    pub fn raw_payload_bytes(&self) -> Option<&[u8]> {
+        if cfg!(feature = "raw-payload") {
+            return Some(&self.raw_payload);
+        }
+        match &self.payload {
+            MiBeaconObjectPayload::Unknown(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+}
+
+// The raw payload is only a copy of the bytes that the payload was parsed from, so it is not
+// compared (which would make equality depend on the `raw-payload` feature).
+impl PartialEq for MiBeaconObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.length == other.length && self.payload == other.payload
+    }
+}
+
+impl Eq for MiBeaconObject {}
+
 /// Base I/O capability types can be divided into two categories: Input and Output.
 ///
 /// *Note:* This field is used only in high security level access.
 #[bitfield]
-#[derive(BinRead, Debug)]
+#[derive(BinRead, BinWrite, Clone, Copy, PartialEq, Eq, Debug)]
 #[br(map = Self::from_bytes)]
+#[bw(map = |&x| Self::into_bytes(x))]
 pub struct MiBeaconBaseIoCapabilities {
     // Basic Input Capabilities
     /// Whether the device can input 6 digits
@@ -1186,9 +2288,9 @@ pub struct MiBeaconBaseIoCapabilities {
 }
 
 /// I/O Capabilities
-#[binread]
-#[br(little)]
-#[derive(Debug)]
+#[binrw]
+#[brw(little)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct MiBeaconIoCapabilities {
     /// Base I/O Capabilities
     pub base_io_capabilities: MiBeaconBaseIoCapabilities,
@@ -1199,8 +2301,9 @@ pub struct MiBeaconIoCapabilities {
 
 /// Capabilities
 #[bitfield]
-#[derive(BinRead, Debug)]
+#[derive(BinRead, BinWrite, Clone, Copy, PartialEq, Eq, Debug)]
 #[br(map = Self::from_bytes)]
+#[bw(map = |&x| Self::into_bytes(x))]
 pub struct MiBeaconCapabilities {
     /// Connectable (temporarily unused)
     pub connectable: bool,
@@ -1208,13 +2311,8 @@ pub struct MiBeaconCapabilities {
     pub centralable: bool,
     /// Encryptable (temporarily unused)
     pub encryptable: bool,
-    /// The Bond Ability field indicates how to determine which device to bond to when there are multiple identical devices nearby.
-    ///
-    /// 0: No binding: APP selects pairing, RSSI meets the pairing requirements;
-    /// 1: Pre-binding: The device confirms pairing, which requires scanning first. The connection is established after the device sends a confirmation packet (solicited in Frame Control);
-    /// 2: Post-binding: Directly connect after scanning, and the device confirms by vibration or other means;
-    /// 3: Combo: Only chips that support Combo are available (this binding method needs to be selected in the Xiaomi IoT Developer Platform and is consistent with this)
-    pub bond_ability: B2,
+    /// The Bond Ability field indicates how to determine which device to bond to when there are multiple identical devices nearby (see [`BondAbility`]).
+    pub bond_ability: BondAbility,
     /// Whether the message includes the I/O Capabilities field
     pub io: bool,
     /// Reserved
@@ -1222,20 +2320,75 @@ pub struct MiBeaconCapabilities {
     reserved: B2,
 }
 
+/// Bond Ability of a MiBeacon device (as indicated by [`MiBeaconCapabilities::bond_ability()`]).
+#[derive(BitfieldSpecifier, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum BondAbility {
+    /// No binding: APP selects pairing, RSSI meets the pairing requirements
+    None,
+    /// Pre-binding: The device confirms pairing, which requires scanning first. The connection is established after the device sends a confirmation packet (solicited in Frame Control)
+    PreBinding,
+    /// Post-binding: Directly connect after scanning, and the device confirms by vibration or other means
+    PostBinding,
+    /// Combo: Only chips that support Combo are available (this binding method needs to be selected in the Xiaomi IoT Developer Platform and is consistent with this)
+    Combo,
+}
+
+/// Summary of the capabilities advertised by a MiBeacon device.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capabilities {
+    /// Whether the device is connectable.
+    pub connectable: bool,
+    /// Whether the device can act as a central.
+    pub centralable: bool,
+    /// Whether the device supports encryption.
+    pub encryptable: bool,
+    /// How to determine which device to bond to.
+    pub bond_ability: BondAbility,
+    /// Whether the advertisement includes [`MiBeaconIoCapabilities`].
+    pub io: bool,
+}
+
+impl From<MiBeaconCapabilities> for Capabilities {
+    fn from(capabilities: MiBeaconCapabilities) -> Self {
+        Self {
+            connectable: capabilities.connectable(),
+            centralable: capabilities.centralable(),
+            encryptable: capabilities.encryptable(),
+            bond_ability: capabilities.bond_ability(),
+            io: capabilities.io(),
+        }
+    }
+}
+
 /// Service Advertisement in the MiBeacon format.
-#[binread]
-#[br(little)]
-#[derive(Debug)]
+#[binrw]
+#[brw(little)]
+#[br(import(lossy: bool))]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct MiBeaconServiceAdvertisement {
     /// Frame Control Header
-    #[br(big)]
+    ///
+    /// Mesh advertisements (i.e. with [`FrameControl::mesh()`] set) use a different layout and are
+    /// rejected with [`ParseError::MeshUnsupported`].
+    #[brw(big)]
+    #[br(assert(!frame_control.mesh(), MeshUnsupportedError))]
     pub frame_control: FrameControl,
     /// Xiaomi Device ID
     pub device_id: u16,
     /// Packet ID
     pub packet_id: u8,
     /// MAC Address (only included if [FrameControl::mac_included()] is `true`)
+    ///
+    /// The address is transmitted in reverse order, but stored in display order.
     #[br(if(frame_control.mac_included()))]
+    #[br(map = |mac_address: Option<MacAddress>| mac_address.map(MacAddress::reversed))]
+    #[bw(map = |mac_address: &Option<MacAddress>| mac_address.map(MacAddress::reversed))]
     pub mac_address: Option<MacAddress>,
     /// Capabilities (only included if [FrameControl::capabilities_included()] is `true`)
     #[br(if(frame_control.capabilities_included()))]
@@ -1245,96 +2398,530 @@ pub struct MiBeaconServiceAdvertisement {
     pub io_capabilities: Option<MiBeaconIoCapabilities>,
     /// Objects (only included if [FrameControl::objects_included()] is `true`)
     #[br(if(frame_control.objects_included()))]
-    #[br(parse_with = until_eof)]
+    #[br(parse_with = parse_objects, args(lossy))]
     pub objects: Vec<MiBeaconObject>,
 }
 
-impl MiBeaconObjectPayload {
-    /// Map this [`MiBeaconObjectPayload`] to one or more [`Sensor Value`] objects.
-    fn to_sensor_events(&self) -> Vec<SensorEvent> {
-        match &self {
-            MiBeaconObjectPayload::Temperature(value) => {
-                vec![SensorEvent::NumericMeasurement {
-                    measurement_type: NumericMeasurementType::Temperature,
-                    value: f64::from(*value) / 10.0,
-                    unit: UnitOfMeasurement::DegreesCelsius,
-                }]
+/// Reads [`MiBeaconObject`]s until the end of the payload.
+///
+/// In contrast to [`binrw::helpers::until_eof`], the declared length of each object is checked
+/// against the remaining bytes before reading the object payload. If `lossy` is `true`, reading
+/// stops at the first object that fails to parse (leaving the reader at the start of that object)
+/// instead of returning an error.
+///
+/// At most [`MiBeaconServiceAdvertisement::MAX_OBJECTS`] objects are read (in lossy mode, reading
+/// stops there as well).
+#[binrw::parser(reader, endian)]
+fn parse_objects(lossy: bool) -> BinResult<Vec<MiBeaconObject>> {
+    let start = reader.stream_position()?;
+    let end = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(start))?;
+
+    let mut objects = Vec::new();
+    loop {
+        let pos = reader.stream_position()?;
+        if pos >= end {
+            return Ok(objects);
+        }
+        if objects.len() >= MiBeaconServiceAdvertisement::MAX_OBJECTS {
+            if lossy {
+                return Ok(objects);
             }
-            MiBeaconObjectPayload::Humidity(value) => {
-                vec![SensorEvent::NumericMeasurement {
-                    measurement_type: NumericMeasurementType::Humidity,
-                    value: f64::from(*value) / 10.0,
-                    unit: UnitOfMeasurement::Percent,
+            return Err(binrw::Error::Custom {
+                pos,
+                err: Box::new(TooManyObjectsError {
+                    limit: MiBeaconServiceAdvertisement::MAX_OBJECTS,
+                }),
+            });
+        }
+
+        match read_object(reader, endian, pos, end) {
+            Ok(object) => objects.push(object),
+            Err(_) if lossy => {
+                reader.seek(SeekFrom::Start(pos))?;
+                return Ok(objects);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Returns the number of bytes between the current position of the reader and its end.
+fn remaining_length<R: Seek>(reader: &mut R) -> BinResult<usize> {
+    let start = reader.stream_position()?;
+    let end = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(start))?;
+    Ok(usize::try_from(end.saturating_sub(start)).unwrap_or(usize::MAX))
+}
+
+/// Reader adapter that ends at the absolute position `end` of the inner reader (e.g. the end of a
+/// frame that is followed by other data), so that [`parse_objects`] stops there.
+///
+/// In contrast to [`binrw::io::TakeSeek`], seeking relative to the end is relative to `end`.
+struct BoundedReader<'a, R> {
+    inner: &'a mut R,
+    end: u64,
+}
+
+impl<R: Read + Seek> Read for BoundedReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> binrw::io::Result<usize> {
+        let remaining = self.end.saturating_sub(self.inner.stream_position()?);
+        let max = buf
+            .len()
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        self.inner.read(&mut buf[..max])
+    }
+}
+
+impl<R: Seek> Seek for BoundedReader<'_, R> {
+    fn seek(&mut self, pos: SeekFrom) -> binrw::io::Result<u64> {
+        match pos {
+            SeekFrom::End(offset) => self
+                .inner
+                .seek(SeekFrom::Start(self.end.saturating_add_signed(offset))),
+            pos => self.inner.seek(pos),
+        }
+    }
+}
+
+/// Reads a single [`MiBeaconObject`] starting at `pos` (after checking its declared length).
+fn read_object<R: Read + Seek>(
+    reader: &mut R,
+    endian: binrw::Endian,
+    pos: u64,
+    end: u64,
+) -> BinResult<MiBeaconObject> {
+    let id = u16::read_options(reader, endian, ())?;
+    let length = u8::read_options(reader, endian, ())?;
+    let remaining = end - reader.stream_position()?;
+    if u64::from(length) > remaining {
+        return Err(binrw::Error::Custom {
+            pos,
+            err: Box::new(ObjectLengthError {
+                object_id: id,
+                length,
+                // Cannot exceed `length`, which is a `u8`.
+                remaining: remaining as usize,
+            }),
+        });
+    }
+
+    reader.seek(SeekFrom::Start(pos))?;
+    MiBeaconObject::read_options(reader, endian, ())
+}
+
+/// Object IDs that are documented (by Xiaomi or in the reverse engineering efforts of other
+/// projects) but are not decoded by this crate yet, so they are parsed as
+/// [`MiBeaconObjectPayload::Unknown`].
+const KNOWN_UNHANDLED_OBJECT_IDS: &[u16] = &[
+    0x4804, // Opening and Closing Status
+    0x4805, // Illuminance
+    0x4806, // Moisture
+    0x4818, // No Motion Time
+    0x4A01, // Low Battery
+    0x4A08, // Motion with Illuminance
+    0x4A0C, // Single Press
+    0x4A0F, // Door Alarm
+    0x4A12, // Opening and Closing
+    0x4A13, // Button
+    0x4A1A, // Door Status
+    0x4C01, // Temperature
+    0x4C02, // Humidity
+    0x4C03, // Battery Level
+    0x4C08, // Humidity
+    0x4C14, // Mode
+    0x4E0C, // Button
+    0x4E0D, // Double Press
+    0x4E0E, // Long Press
+    0x4E1C, // Device Reset
+];
+
+impl MiBeaconObjectPayload {
+    /// Returns `true` if the object ID is not supported by this crate (i.e. the payload is
+    /// [`MiBeaconObjectPayload::Unknown`]).
+    #[must_use]
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Self::Unknown(_))
+    }
+
+    /// Returns `true` if the object ID is known, i.e. it is either decoded by this crate or it is
+    /// documented but not decoded yet.
+    ///
+    /// This can be used to tell apart [`MiBeaconObjectPayload::Unknown`] payloads that are
+    /// expected (because decoding is not implemented yet) from truly unknown object IDs.
+    #[must_use]
+    pub fn is_known_id(id: u16) -> bool {
+        Self::is_handled_id(id) || KNOWN_UNHANDLED_OBJECT_IDS.contains(&id)
+    }
+
+    /// Returns `true` if objects with this ID are decoded by this crate.
+    fn is_handled_id(id: u16) -> bool {
+        matches!(
+            id,
+            0x0001..=0x0012
+                | 0x1001..=0x100A
+                | 0x100D..=0x101C
+                | 0x2000..=0x2003
+                | 0x3000..=0x3003
+                | 0x4803
+        )
+    }
+
+    /// Get the resolution of the numeric measurements (in the unit of the corresponding
+    /// [`SensorEvent`]) that are parsed from this payload.
+    fn resolution(&self) -> Option<f64> {
+        match self {
+            MiBeaconObjectPayload::Temperature(_)
+            | MiBeaconObjectPayload::Humidity(_)
+            | MiBeaconObjectPayload::TemperatureAndHumidity { .. } => Some(0.1),
+            MiBeaconObjectPayload::FormaldehydeConcentration(_) => Some(0.01),
+            MiBeaconObjectPayload::FormaldehydeConcentrationNew(_)
+            | MiBeaconObjectPayload::Weight(_) => Some(0.001),
+            MiBeaconObjectPayload::PowerAndTemperature { .. }
+            | MiBeaconObjectPayload::Illuminance(_)
+            | MiBeaconObjectPayload::Moisture(_)
+            | MiBeaconObjectPayload::Conductivity(_)
+            | MiBeaconObjectPayload::BatteryPower(_)
+            | MiBeaconObjectPayload::BatteryLevel(_)
+            | MiBeaconObjectPayload::RemainingSupplies(_)
+            | MiBeaconObjectPayload::ToothbrushEvent { .. }
+            | MiBeaconObjectPayload::XiaobelToothbrushEvent { .. } => Some(1.0),
+            _ => None,
+        }
+    }
+
+    /// Map this [`MiBeaconObjectPayload`] to one or more [`SensorEvent`]s, without any information
+    /// about the device that sent it (see [`Self::to_sensor_events`]).
+    #[must_use]
+    pub fn sensor_events(&self) -> Vec<SensorEvent> {
+        self.to_sensor_events(&ParseContext::default())
+    }
+
+    /// Map this [`MiBeaconObjectPayload`] to one or more [`SensorEvent`]s.
+    ///
+    /// The context of the advertisement is used to handle device-specific quirks (e.g. key events
+    /// whose meaning depends on the device model).
+    #[must_use]
+    pub fn to_sensor_events(&self, ctx: &ParseContext) -> Vec<SensorEvent> {
+        self.iter_sensor_events(ctx).collect()
+    }
+
+    /// Yields the [`SensorEvent`]s of this [`MiBeaconObjectPayload`] (like
+    /// [`Self::to_sensor_events`], but without allocating).
+    pub fn iter_sensor_events(&self, ctx: &ParseContext) -> ObjectSensorEvents {
+        let device_id = ctx.device_id;
+        match &self {
+            MiBeaconObjectPayload::Temperature(value) => {
+                object_sensor_events![SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Temperature,
+                    value: f64::from(*value) / 10.0,
+                    unit: UnitOfMeasurement::DegreesCelsius,
+                }]
+            }
+            MiBeaconObjectPayload::PowerAndTemperature { power, temperature } => {
+                let temperature_event = SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Temperature,
+                    value: f64::from(*temperature),
+                    unit: UnitOfMeasurement::DegreesCelsius,
+                };
+                let power_event = SensorEvent::BinaryMeasurement {
+                    measurement_type: BinaryMeasurementType::Power,
+                    value: *power != 0,
+                };
+                match KettleState::from_power_and_temperature(*power, *temperature) {
+                    Some(state) => object_sensor_events![
+                        temperature_event,
+                        power_event,
+                        SensorEvent::DiscreteEvent(DiscreteEvent::Kettle { state }),
+                    ],
+                    None => object_sensor_events![temperature_event, power_event],
+                }
+            }
+            MiBeaconObjectPayload::Humidity(value) => {
+                object_sensor_events![SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Humidity,
+                    value: f64::from(*value) / 10.0,
+                    unit: UnitOfMeasurement::Percent,
                 }]
             }
+            MiBeaconObjectPayload::TemperatureAndHumidity {
+                temperature,
+                humidity,
+            } => {
+                object_sensor_events![
+                    SensorEvent::NumericMeasurement {
+                        measurement_type: NumericMeasurementType::Temperature,
+                        value: f64::from(*temperature) / 10.0,
+                        unit: UnitOfMeasurement::DegreesCelsius,
+                    },
+                    SensorEvent::NumericMeasurement {
+                        measurement_type: NumericMeasurementType::Humidity,
+                        value: f64::from(*humidity) / 10.0,
+                        unit: UnitOfMeasurement::Percent,
+                    },
+                ]
+            }
             MiBeaconObjectPayload::Illuminance(value) => {
-                vec![SensorEvent::NumericMeasurement {
+                object_sensor_events![SensorEvent::NumericMeasurement {
                     measurement_type: NumericMeasurementType::Illuminance,
                     value: f64::from(value.as_u32()),
                     unit: UnitOfMeasurement::Lux,
                 }]
             }
             MiBeaconObjectPayload::Moisture(value) => {
-                vec![SensorEvent::NumericMeasurement {
+                object_sensor_events![SensorEvent::NumericMeasurement {
                     measurement_type: NumericMeasurementType::Moisture,
                     value: f64::from(*value),
                     unit: UnitOfMeasurement::Percent,
                 }]
             }
             MiBeaconObjectPayload::Conductivity(value) => {
-                vec![SensorEvent::NumericMeasurement {
+                object_sensor_events![SensorEvent::NumericMeasurement {
                     measurement_type: NumericMeasurementType::Conductivity,
                     value: f64::from(*value),
                     unit: UnitOfMeasurement::MicrosiemensPerCentimeter,
                 }]
             }
             MiBeaconObjectPayload::FormaldehydeConcentration(value) => {
-                vec![SensorEvent::NumericMeasurement {
+                object_sensor_events![SensorEvent::NumericMeasurement {
                     measurement_type: NumericMeasurementType::FormaldehydeConcentration,
                     value: f64::from(*value) / 100.0,
                     unit: UnitOfMeasurement::MilligramPerCubicMeter,
                 }]
             }
             MiBeaconObjectPayload::FormaldehydeConcentrationNew(value) => {
-                vec![SensorEvent::NumericMeasurement {
+                object_sensor_events![SensorEvent::NumericMeasurement {
                     measurement_type: NumericMeasurementType::FormaldehydeConcentration,
                     value: f64::from(*value) / 1000.0,
                     unit: UnitOfMeasurement::MilligramPerCubicMeter,
                 }]
             }
-            MiBeaconObjectPayload::BatteryPower(value) => {
-                vec![SensorEvent::NumericMeasurement {
+            MiBeaconObjectPayload::BatteryPower(value)
+            | MiBeaconObjectPayload::BatteryLevel(value) => {
+                object_sensor_events![SensorEvent::NumericMeasurement {
                     measurement_type: NumericMeasurementType::BatteryPower,
                     value: f64::from(*value),
                     unit: UnitOfMeasurement::Percent,
                 }]
             }
             MiBeaconObjectPayload::RemainingSupplies(value) => {
-                vec![SensorEvent::NumericMeasurement {
+                object_sensor_events![SensorEvent::NumericMeasurement {
                     measurement_type: NumericMeasurementType::RemainingSupplies,
                     value: f64::from(*value),
                     unit: UnitOfMeasurement::Percent,
                 }]
             }
             MiBeaconObjectPayload::Weight(value) => {
-                vec![SensorEvent::NumericMeasurement {
+                object_sensor_events![SensorEvent::NumericMeasurement {
                     measurement_type: NumericMeasurementType::Weight,
-                    value: f64::from(*value),
+                    value: f64::from(*value) / 1000.0,
                     unit: UnitOfMeasurement::Kilogram,
                 }]
             }
+            MiBeaconObjectPayload::Lock(state) => {
+                object_sensor_events![
+                    SensorEvent::BinaryMeasurement {
+                        measurement_type: BinaryMeasurementType::Lock,
+                        value: state.is_locked(),
+                    },
+                    SensorEvent::BinaryMeasurement {
+                        measurement_type: BinaryMeasurementType::AntiLock,
+                        value: state.dead_tongue_ejected(),
+                    },
+                    SensorEvent::BinaryMeasurement {
+                        measurement_type: BinaryMeasurementType::ChildLock,
+                        value: state.child_lock_ejected(),
+                    },
+                ]
+            }
+            MiBeaconObjectPayload::FingerprintEvent {
+                key_id,
+                matching_result,
+            } => {
+                object_sensor_events![SensorEvent::DiscreteEvent(DiscreteEvent::Fingerprint {
+                    key_id: *key_id,
+                    matching_result: *matching_result,
+                })]
+            }
+            MiBeaconObjectPayload::LockEvent(event) => {
+                object_sensor_events![SensorEvent::DiscreteEvent(DiscreteEvent::Lock {
+                    action: event.operation.action(),
+                    method: event.operation.method(),
+                    key: event.key_category(),
+                    timestamp: event.timestamp,
+                })]
+            }
+            MiBeaconObjectPayload::ConnectEvent(object_id) => {
+                object_sensor_events![SensorEvent::DiscreteEvent(DiscreteEvent::Connect {
+                    object_id: *object_id,
+                })]
+            }
+            MiBeaconObjectPayload::SimplePairingEvent(object_id) => {
+                object_sensor_events![SensorEvent::DiscreteEvent(DiscreteEvent::SimplePairing {
+                    object_id: *object_id,
+                })]
+            }
+            MiBeaconObjectPayload::ProximityEvent(object_id) => {
+                object_sensor_events![SensorEvent::DiscreteEvent(DiscreteEvent::Proximity {
+                    object_id: *object_id,
+                })]
+            }
+            MiBeaconObjectPayload::KeepAwayEvent(object_id) => {
+                object_sensor_events![SensorEvent::DiscreteEvent(DiscreteEvent::KeepAway {
+                    object_id: *object_id,
+                })]
+            }
+            MiBeaconObjectPayload::LockEventDeprecated(object_id) => {
+                object_sensor_events![SensorEvent::DiscreteEvent(DiscreteEvent::LockDeprecated {
+                    object_id: *object_id,
+                })]
+            }
+            MiBeaconObjectPayload::ArmingEvent { status, timestamp } => {
+                object_sensor_events![SensorEvent::DiscreteEvent(DiscreteEvent::Arming {
+                    status: *status,
+                    timestamp: *timestamp,
+                })]
+            }
+            MiBeaconObjectPayload::DoorbellCameraEvent(event_type) => {
+                object_sensor_events![SensorEvent::DiscreteEvent(DiscreteEvent::Doorbell {
+                    event_type: *event_type,
+                })]
+            }
+            MiBeaconObjectPayload::FlowerAndGrassDetectorEvent(event) => {
+                object_sensor_events![SensorEvent::BinaryMeasurement {
+                    measurement_type: BinaryMeasurementType::PluggedIn,
+                    value: *event == FlowerAndGrassDetectorEvent::Normal,
+                }]
+            }
+            MiBeaconObjectPayload::QuingpingSensorLocationEvent(location) => {
+                object_sensor_events![SensorEvent::DiscreteEvent(DiscreteEvent::SensorLocation {
+                    location: *location,
+                })]
+            }
+            MiBeaconObjectPayload::QuingpingPomodoroEvent(phase) => {
+                object_sensor_events![SensorEvent::DiscreteEvent(DiscreteEvent::Pomodoro {
+                    phase: *phase,
+                })]
+            }
+            MiBeaconObjectPayload::KeyEvent {
+                key,
+                value,
+                event_type,
+            } => key_events(device_id, *key, *value, *event_type),
+            MiBeaconObjectPayload::ToothbrushEvent { event_type, score } => {
+                toothbrush_events(*event_type, None, *score)
+            }
+            MiBeaconObjectPayload::XiaobelToothbrushEvent {
+                event_type,
+                timestamp,
+                score,
+            } => toothbrush_events(*event_type, Some(*timestamp), *score),
             _ => {
-                warn!("Ignoring unhandled MiBeacon object payload: {:?}", &self);
-                vec![]
+                log_warn!("Ignoring unhandled MiBeacon object payload: {:?}", &self);
+                object_sensor_events![]
             }
         }
     }
 }
 
 impl MiBeaconServiceAdvertisement {
+    /// Maximum number of objects in an advertisement.
+    ///
+    /// Legacy BLE advertisements carry at most 31 bytes and each object takes at least 3 bytes,
+    /// so real advertisements contain far fewer objects. Parsing a payload with more objects fails
+    /// with [`ParseError::TooManyObjects`].
+    pub const MAX_OBJECTS: usize = 16;
+
     /// Parses a [MiBeaconServiceAdvertisement] from a byte slice.
     pub fn from_slice(slice: &[u8]) -> Result<Self, ParseError> {
-        Ok(Self::read(&mut Cursor::new(slice))?)
+        Self::read_from(&mut Cursor::new(slice))
+    }
+
+    /// Parses a [MiBeaconServiceAdvertisement] from a reader, starting at its current position.
+    ///
+    /// Objects are read until the end of the reader, so the advertisement must be the last thing
+    /// in the stream. Use [`Self::read_from_bounded`] if the length of the advertisement is known
+    /// (e.g. to parse back-to-back frames from one buffer).
+    pub fn read_from<R: Read + Seek>(reader: &mut R) -> Result<Self, ParseError> {
+        let found = remaining_length(reader).map_err(|err| ParseError::from_binrw(err, 0, None))?;
+        Self::read(reader).map_err(|err| ParseError::from_binrw(err, found, None))
+    }
+
+    /// Parses a [MiBeaconServiceAdvertisement] of `len` bytes from a reader, starting at its
+    /// current position.
+    ///
+    /// Objects are only read up to the end of the advertisement, so that the reader is positioned
+    /// at the data following it afterwards (e.g. the next frame). If fewer than `len` bytes are
+    /// left in the reader, [`ParseError::Truncated`] is returned.
+    pub fn read_from_bounded<R: Read + Seek>(
+        reader: &mut R,
+        len: usize,
+    ) -> Result<Self, ParseError> {
+        let to_parse_error = |err| ParseError::from_binrw(err, 0, None);
+        let start = reader
+            .stream_position()
+            .map_err(|err| to_parse_error(binrw::Error::Io(err)))?;
+        let found = remaining_length(reader).map_err(to_parse_error)?;
+        if found < len {
+            return Err(ParseError::Truncated {
+                expected: Some(len),
+                found,
+            });
+        }
+        let mut bounded = BoundedReader {
+            inner: reader,
+            // Cannot exceed the length of the reader.
+            end: start + len as u64,
+        };
+        Self::read(&mut bounded).map_err(|err| ParseError::from_binrw(err, len, None))
+    }
+
+    /// Parses a [MiBeaconServiceAdvertisement] from a byte slice, but stops reading objects at the
+    /// first object that fails to parse instead of discarding the whole advertisement.
+    ///
+    /// Returns the advertisement (with all objects preceding the malformed one) and the number of
+    /// skipped bytes at the end of the slice. An error is only returned if the frame header is
+    /// invalid.
+    pub fn from_slice_lossy(slice: &[u8]) -> Result<(Self, usize), ParseError> {
+        let mut cursor = Cursor::new(slice);
+        let advertisement = Self::read_args(&mut cursor, (true,))
+            .map_err(|err| ParseError::from_binrw(err, slice.len(), None))?;
+        // Cannot exceed the slice length.
+        let skipped = slice.len() - cursor.position() as usize;
+        Ok((advertisement, skipped))
+    }
+
+    /// Parses a [MiBeaconServiceAdvertisement] from a byte slice, tolerating frames whose header
+    /// flags claim optional fields that are not actually present.
+    ///
+    /// Some third-party firmwares set [`FrameControl::mac_included()`] or
+    /// [`FrameControl::capabilities_included()`] although the frame continues with the objects
+    /// directly. If parsing fails, the frame is parsed again as if both flags were unset (and the
+    /// returned [`frame_control`](Self::frame_control) has them cleared). If that fails as well,
+    /// the original error is returned.
+    ///
+    /// Note that a misaligned frame may still parse successfully by accident, so this should only
+    /// be used for devices that are known to send such frames.
+    pub fn from_slice_lenient(slice: &[u8]) -> Result<Self, ParseError> {
+        let err = match Self::from_slice(slice) {
+            Ok(advertisement) => return Ok(advertisement),
+            Err(err) => err,
+        };
+        let Some(&[first, second]) = slice.get(..2) else {
+            return Err(err);
+        };
+        let frame_control = FrameControl::from_bytes([first, second]);
+        if !frame_control.objects_included()
+            || !(frame_control.mac_included() || frame_control.capabilities_included())
+        {
+            return Err(err);
+        }
+        let frame_control = frame_control
+            .with_mac_included(false)
+            .with_capabilities_included(false);
+        let mut bytes = slice.to_vec();
+        bytes[..2].copy_from_slice(&frame_control.into_bytes());
+        Self::from_slice(&bytes).map_err(|_| err)
     }
 
     /// Get device type of advertisement sender.
@@ -1342,17 +2929,396 @@ impl MiBeaconServiceAdvertisement {
         DEVICE_TYPES.get(&self.device_id)
     }
 
+    /// Get the MiBeacon protocol version (see [`FrameControl::version()`]).
+    #[must_use]
+    pub fn version(&self) -> u8 {
+        self.frame_control.version()
+    }
+
     /// Yields the object paylads for the service advertisement.
     pub fn iter_sensor_events(&self) -> impl Iterator<Item = SensorEvent> + '_ {
+        let ctx = self.context();
         self.objects
             .iter()
-            .flat_map(|obj| obj.payload.to_sensor_events().into_iter())
+            .flat_map(move |obj| obj.payload.iter_sensor_events(&ctx))
+    }
+
+    /// Yields the sensor events parsed from the objects contained in the service advertisement,
+    /// together with the ID of the object that each event was parsed from and the resolution of
+    /// numeric measurements.
+    pub fn iter_readings(&self) -> impl Iterator<Item = Reading> + '_ {
+        let ctx = self.context();
+        self.objects.iter().flat_map(move |obj| {
+            obj.payload.iter_sensor_events(&ctx).map(move |event| {
+                let resolution = match event {
+                    SensorEvent::NumericMeasurement { .. } => obj.payload.resolution(),
+                    _ => None,
+                };
+                Reading {
+                    event,
+                    source_object_id: Some(obj.id),
+                    is_final: None,
+                    resolution,
+                }
+            })
+        })
+    }
+
+    /// Get the [`ParseContext`] that is used to interpret the objects of this advertisement.
+    #[must_use]
+    pub fn context(&self) -> ParseContext {
+        ParseContext::from(self)
+    }
+
+    /// Get a summary of the [`capabilities`](Self::capabilities) (if included).
+    #[must_use]
+    pub fn capabilities_summary(&self) -> Option<Capabilities> {
+        self.capabilities.map(Capabilities::from)
+    }
+
+    /// Yields the ID, length and payload of each object contained in the service advertisement.
+    pub fn iter_objects(&self) -> impl Iterator<Item = (u16, u8, &MiBeaconObjectPayload)> + '_ {
+        self.objects
+            .iter()
+            .map(|object| (object.id, object.length, &object.payload))
+    }
+
+    /// Collects the sensor events for the service advertisement, together with the object
+    /// payloads that did not yield any sensor events.
+    pub fn sensor_events_with_unhandled(&self) -> (Vec<SensorEvent>, Vec<&MiBeaconObjectPayload>) {
+        let (events, unhandled) = self.sensor_events_with_unhandled_objects();
+        let unhandled = unhandled.into_iter().map(|obj| &obj.payload).collect();
+        (events, unhandled)
+    }
+
+    /// Collects the sensor events for the service advertisement, together with the objects that
+    /// did not yield any sensor events and the reason why (e.g. for diagnostics without parsing
+    /// log messages).
+    #[must_use]
+    pub fn try_iter_sensor_events(&self) -> (Vec<SensorEvent>, Vec<UnhandledObject>) {
+        let (events, unhandled) = self.sensor_events_with_unhandled_objects();
+        let unhandled = unhandled.into_iter().map(UnhandledObject::new).collect();
+        (events, unhandled)
+    }
+
+    /// Collects the sensor events for the service advertisement, together with the objects that
+    /// did not yield any sensor events.
+    fn sensor_events_with_unhandled_objects(&self) -> (Vec<SensorEvent>, Vec<&MiBeaconObject>) {
+        let ctx = self.context();
+        let mut events = Vec::new();
+        let mut unhandled = Vec::new();
+        for obj in &self.objects {
+            let mut obj_events = obj.payload.iter_sensor_events(&ctx).peekable();
+            if obj_events.peek().is_none() {
+                unhandled.push(obj);
+            } else {
+                events.extend(obj_events);
+            }
+        }
+        (events, unhandled)
+    }
+}
+
+/// Tracks the last seen [`MiBeaconServiceAdvertisement::packet_id`] per device to skip repeated
+/// advertisements (devices usually broadcast the same frame many times).
+///
+/// The key is usually the [`MacAddress`] of the device, but any orderable type (e.g. the device
+/// ID) can be used.
+#[derive(Clone, Debug)]
+pub struct SequenceTracker<K = MacAddress> {
+    last_packet_ids: BTreeMap<K, u8>,
+}
+
+impl<K: Ord> SequenceTracker<K> {
+    /// Creates an empty tracker.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            last_packet_ids: BTreeMap::new(),
+        }
+    }
+
+    /// Returns `true` if `packet_id` differs from the last packet ID seen for `key` (and remembers
+    /// it), or `false` if the packet is a repeat.
+    ///
+    /// Since the packet ID is only 8 bits wide, it wraps around from 255 to 0. Only consecutive
+    /// repeats are detected, so a wrapped-around (or reset) counter is still treated as new.
+    pub fn is_new(&mut self, key: K, packet_id: u8) -> bool {
+        self.last_packet_ids.insert(key, packet_id) != Some(packet_id)
+    }
+}
+
+impl<K: Ord> Default for SequenceTracker<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Key used by the [`Deduplicator`] to identify the sender of an advertisement.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum DeduplicationKey {
+    /// The MAC address included in the advertisement.
+    MacAddress(MacAddress),
+    /// The device ID, used if the advertisement does not include the MAC address.
+    DeviceId(u16),
+}
+
+impl From<&MiBeaconServiceAdvertisement> for DeduplicationKey {
+    fn from(advertisement: &MiBeaconServiceAdvertisement) -> Self {
+        match advertisement.mac_address {
+            Some(mac_address) => Self::MacAddress(mac_address),
+            None => Self::DeviceId(advertisement.device_id),
+        }
+    }
+}
+
+/// Drops repeated [`MiBeaconServiceAdvertisement`]s, based on a [`SequenceTracker`].
+///
+/// Advertisements are keyed by their MAC address (or the device ID if the MAC address is not
+/// included, see [`DeduplicationKey`]) and their packet ID.
+#[derive(Clone, Debug, Default)]
+pub struct Deduplicator {
+    tracker: SequenceTracker<DeduplicationKey>,
+    repeats: BTreeMap<DeduplicationKey, usize>,
+    window: Option<usize>,
+}
+
+impl Deduplicator {
+    /// Creates a deduplicator that drops all repeats of a packet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a deduplicator that only drops up to `window - 1` consecutive repeats of a packet,
+    /// i.e. every `window`-th repeat is passed through again.
+    ///
+    /// This is useful to still get a periodic update from devices that don't increment the packet
+    /// ID when the measured value did not change.
+    #[must_use]
+    pub fn with_window(window: usize) -> Self {
+        Self {
+            window: Some(window),
+            ..Self::default()
+        }
+    }
+
+    /// Returns `true` if the advertisement should be processed, or `false` if it is a repeat.
+    pub fn is_new(&mut self, advertisement: &MiBeaconServiceAdvertisement) -> bool {
+        let key = DeduplicationKey::from(advertisement);
+        if self.tracker.is_new(key, advertisement.packet_id) {
+            self.repeats.remove(&key);
+            return true;
+        }
+
+        let Some(window) = self.window else {
+            return false;
+        };
+        let repeats = self.repeats.entry(key).or_insert(0);
+        *repeats += 1;
+        if *repeats >= window {
+            *repeats = 0;
+            return true;
+        }
+        false
+    }
+
+    /// Filters repeated advertisements out of `advertisements`.
+    pub fn filter<'a, I>(&'a mut self, advertisements: I) -> impl Iterator<Item = I::Item> + 'a
+    where
+        I: IntoIterator + 'a,
+        I::Item: Borrow<MiBeaconServiceAdvertisement>,
+    {
+        advertisements
+            .into_iter()
+            .filter(move |advertisement| self.is_new(advertisement.borrow()))
+    }
+}
+
+/// Builder for [`MiBeaconServiceAdvertisement`]s, e.g. to create test fixtures without writing
+/// the raw bytes by hand.
+///
+/// The [`FrameControl::mac_included()`], [`FrameControl::capabilities_included()`] and
+/// [`FrameControl::objects_included()`] flags (as well as [`MiBeaconCapabilities::io()`]) are set
+/// automatically, depending on which fields were provided.
+///
+/// Only available with the `test-util` feature.
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Clone, Debug)]
+pub struct MiBeaconBuilder {
+    frame_control: FrameControl,
+    device_id: u16,
+    packet_id: u8,
+    mac_address: Option<MacAddress>,
+    capabilities: Option<MiBeaconCapabilities>,
+    io_capabilities: Option<MiBeaconIoCapabilities>,
+    objects: Vec<MiBeaconObject>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl MiBeaconBuilder {
+    /// Creates a builder for an advertisement of the device with the given ID (using MiBeacon
+    /// version 5).
+    #[must_use]
+    pub fn new(device_id: u16) -> Self {
+        Self {
+            frame_control: FrameControl::new().with_version(5),
+            device_id,
+            packet_id: 0,
+            mac_address: None,
+            capabilities: None,
+            io_capabilities: None,
+            objects: Vec::new(),
+        }
+    }
+
+    /// Sets the MiBeacon version (see [`FrameControl::version()`]).
+    #[must_use]
+    pub fn version(mut self, version: u8) -> Self {
+        self.frame_control.set_version(version);
+        self
+    }
+
+    /// Sets the authentication mode (see [`FrameControl::auth_mode()`]).
+    #[must_use]
+    pub fn auth_mode(mut self, auth_mode: AuthMode) -> Self {
+        self.frame_control.set_auth_mode(auth_mode);
+        self
+    }
+
+    /// Sets the [`FrameControl::is_encrypted()`] flag.
+    #[must_use]
+    pub fn encrypted(mut self, is_encrypted: bool) -> Self {
+        self.frame_control.set_is_encrypted(is_encrypted);
+        self
+    }
+
+    /// Sets the [`FrameControl::mesh()`] flag.
+    #[must_use]
+    pub fn mesh(mut self, mesh: bool) -> Self {
+        self.frame_control.set_mesh(mesh);
+        self
+    }
+
+    /// Sets the [`FrameControl::registered()`] flag.
+    #[must_use]
+    pub fn registered(mut self, registered: bool) -> Self {
+        self.frame_control.set_registered(registered);
+        self
+    }
+
+    /// Sets the [`FrameControl::solicited()`] flag.
+    #[must_use]
+    pub fn solicited(mut self, solicited: bool) -> Self {
+        self.frame_control.set_solicited(solicited);
+        self
+    }
+
+    /// Sets the three reserved bits of the frame control header (which are set by some older
+    /// devices, e.g. the HHCCJCY01).
+    #[must_use]
+    pub fn reserved_bits(mut self, reserved: u8) -> Self {
+        self.frame_control.set_reserved(reserved);
+        self
+    }
+
+    /// Sets the packet ID.
+    #[must_use]
+    pub fn packet_id(mut self, packet_id: u8) -> Self {
+        self.packet_id = packet_id;
+        self
+    }
+
+    /// Includes the MAC address (in display order; it is reversed when writing the frame).
+    #[must_use]
+    pub fn mac_address(mut self, mac_address: impl Into<MacAddress>) -> Self {
+        self.mac_address = Some(mac_address.into());
+        self
+    }
+
+    /// Includes the capabilities.
+    #[must_use]
+    pub fn capabilities(mut self, capabilities: MiBeaconCapabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    /// Includes the I/O capabilities (this implies that capabilities are included).
+    #[must_use]
+    pub fn io_capabilities(mut self, io_capabilities: MiBeaconIoCapabilities) -> Self {
+        self.io_capabilities = Some(io_capabilities);
+        self
+    }
+
+    /// Appends an object with the given ID and payload (the object length is calculated from the
+    /// payload).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload is longer than 255 bytes.
+    #[must_use]
+    pub fn object(mut self, id: u16, payload: MiBeaconObjectPayload) -> Self {
+        self.objects.push(MiBeaconObject::new(id, payload));
+        self
+    }
+
+    /// Builds the advertisement.
+    #[must_use]
+    pub fn build(self) -> MiBeaconServiceAdvertisement {
+        let capabilities = match (self.capabilities, &self.io_capabilities) {
+            (Some(capabilities), io) => Some(capabilities.with_io(io.is_some())),
+            (None, Some(_)) => Some(MiBeaconCapabilities::new().with_io(true)),
+            (None, None) => None,
+        };
+        let frame_control = self
+            .frame_control
+            .with_mac_included(self.mac_address.is_some())
+            .with_capabilities_included(capabilities.is_some())
+            .with_objects_included(!self.objects.is_empty());
+        MiBeaconServiceAdvertisement {
+            frame_control,
+            device_id: self.device_id,
+            packet_id: self.packet_id,
+            mac_address: self.mac_address,
+            capabilities,
+            io_capabilities: self.io_capabilities,
+            objects: self.objects,
+        }
+    }
+
+    /// Builds the advertisement and serializes it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the advertisement cannot be written (which should not happen).
+    #[must_use]
+    pub fn to_bytes(self) -> Vec<u8> {
+        let mut writer = Cursor::new(Vec::new());
+        self.build()
+            .write(&mut writer)
+            .expect("writing to a Vec should not fail");
+        writer.into_inner()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::MiBeaconServiceAdvertisement;
+    use super::{
+        all_device_types, requires_bindkey, ArmingEventStatus, AuthMode, BondAbility,
+        ButtonEventType, Capabilities, Deduplicator, DimmerAction, DoorEvent, DoorbellCameraEvent,
+        FingerprintEventKeyId, FingerprintEventMatchingResult, FrameControl, Gesture, KettleState,
+        LockAction, LockEvent, LockEventOperation, LockKeyCategory, LockMethod, LockState,
+        MacAddress, MacAddressParseError, MiBeaconBuilder, MiBeaconCapabilities, MiBeaconObject,
+        MiBeaconObjectPayload, MiBeaconServiceAdvertisement, ParseContext, QuingpingPomodoroEvent,
+        QuingpingSensorLocationEvent, RemoteButton, SequenceTracker, ToothbrushEventType,
+        UnhandledObject, UnhandledReason,
+    };
+    use crate::sensor::{
+        BinaryMeasurementType, DiscreteEvent, NumericMeasurementType, SensorEvent,
+        UnitOfMeasurement,
+    };
+    use crate::{ParseError, U24};
+    use binrw::io::Cursor;
 
     const HHCCJCY01_TEMPERATURE_READING: [u8; 17] = [
         0x71, 0x20, 0x98, 0x00, 0xB1, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x04, 0x10, 0x02,
@@ -1370,6 +3336,97 @@ mod tests {
         0x71, 0x20, 0x98, 0x00, 0xD7, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x08, 0x10, 0x01,
         0x17,
     ];
+    const HHCCJCY01_TEMPERATURE_RSSI_UNKNOWN_READING: [u8; 26] = [
+        0x71, 0x20, 0x98, 0x00, 0xB2, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x04, 0x10, 0x02,
+        0xEC, 0x00, 0x03, 0x10, 0x01, 0xC4, 0x00, 0x2F, 0x02, 0xAA, 0xBB,
+    ];
+    const MIBEACON_CAPABILITIES_WITH_IO: [u8; 14] = [
+        0x30, 0x50, 0x98, 0x00, 0x01, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x2D, 0x11, 0x00,
+    ];
+    const CGG1_TEMPERATURE_HUMIDITY_READING: [u8; 19] = [
+        0x71, 0x20, 0x47, 0x03, 0x12, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x0D, 0x10, 0x04,
+        0xE1, 0x00, 0x8A, 0x01,
+    ];
+    const XMZNMS04LM_LOCK_READING: [u8; 16] = [
+        0x71, 0x20, 0x84, 0x07, 0x2A, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x0E, 0x10, 0x01,
+        0x0D,
+    ];
+    const XMZNMS04LM_LOCK_EVENT: [u8; 24] = [
+        0x71, 0x20, 0x84, 0x07, 0x2B, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x0B, 0x00, 0x09,
+        0x25, 0x02, 0x00, 0x01, 0x80, 0x00, 0x00, 0x00, 0x66,
+    ];
+    const WEIGHT_READING: [u8; 17] = [
+        0x71, 0x20, 0x00, 0x00, 0x39, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x1A, 0x10, 0x02,
+        0xB8, 0x0B,
+    ];
+    const GCLS002_UNPLUGGED_EVENT: [u8; 16] = [
+        0x71, 0x20, 0xBC, 0x03, 0x38, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x00, 0x30, 0x01,
+        0x01,
+    ];
+    const QINGPING_SENSOR_LOCATION_EVENT: [u8; 16] = [
+        0x71, 0x20, 0x00, 0x00, 0x36, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x01, 0x30, 0x01,
+        0x00,
+    ];
+    const QINGPING_POMODORO_EVENT: [u8; 16] = [
+        0x71, 0x20, 0x00, 0x00, 0x37, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x02, 0x30, 0x01,
+        0x00,
+    ];
+    const CONNECT_EVENT: [u8; 17] = [
+        0x71, 0x20, 0x00, 0x00, 0x35, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x01, 0x00, 0x02,
+        0x01, 0x10,
+    ];
+    const ARMING_EVENT: [u8; 16] = [
+        0x71, 0x20, 0x00, 0x00, 0x33, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x08, 0x00, 0x01,
+        0x00,
+    ];
+    const DISARMING_EVENT_WITH_TIMESTAMP: [u8; 20] = [
+        0x71, 0x20, 0x00, 0x00, 0x34, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x08, 0x00, 0x05,
+        0x01, 0x00, 0x00, 0x00, 0x66,
+    ];
+    const MAOYAN_DOORBELL_RINGING_EVENT: [u8; 16] = [
+        0x71, 0x20, 0x00, 0x00, 0x31, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x11, 0x00, 0x01,
+        0x02,
+    ];
+    const MAOYAN_DOORBELL_DURESS_ALARM_EVENT: [u8; 16] = [
+        0x71, 0x20, 0x00, 0x00, 0x32, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x11, 0x00, 0x01,
+        0x05,
+    ];
+    const YLKG07YL_ROTATE_RIGHT_EVENT: [u8; 18] = [
+        0x71, 0x20, 0xB6, 0x03, 0x01, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x01, 0x10, 0x03,
+        0x00, 0x02, 0x04,
+    ];
+    const YM_K1501_HEATING_READING: [u8; 17] = [
+        0x71, 0x20, 0x83, 0x00, 0x01, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x05, 0x10, 0x02,
+        0x01, 0x28,
+    ];
+    const YLYK01YL_KEY_EVENT: [u8; 18] = [
+        0x71, 0x20, 0x53, 0x01, 0x01, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x01, 0x10, 0x03,
+        0x03, 0x00, 0x01,
+    ];
+    const M1S_T500_TOOTHBRUSH_EVENT: [u8; 16] = [
+        0x71, 0x20, 0x89, 0x04, 0x2F, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x10, 0x00, 0x01,
+        0x00,
+    ];
+    const M1S_T500_TOOTHBRUSH_SCORE_EVENT: [u8; 17] = [
+        0x71, 0x20, 0x89, 0x04, 0x30, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x10, 0x00, 0x02,
+        0x01, 0x5A,
+    ];
+    const M1S_T500_XIAOBEL_TOOTHBRUSH_EVENT: [u8; 20] = [
+        0x71, 0x20, 0x89, 0x04, 0x2D, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x03, 0x30, 0x05,
+        0x00, 0x00, 0x00, 0x00, 0x66,
+    ];
+    const M1S_T500_XIAOBEL_TOOTHBRUSH_SCORE_EVENT: [u8; 21] = [
+        0x71, 0x20, 0x89, 0x04, 0x2E, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x03, 0x30, 0x06,
+        0x01, 0x3C, 0x00, 0x00, 0x66, 0x5A,
+    ];
+    const XMZNMS04LM_FINGERPRINT_ADMIN_MATCH: [u8; 20] = [
+        0x71, 0x20, 0x84, 0x07, 0x2C, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x06, 0x00, 0x05,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+    const XMZNMS04LM_FINGERPRINT_LOW_QUALITY: [u8; 20] = [
+        0x71, 0x20, 0x84, 0x07, 0x2D, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x06, 0x00, 0x05,
+        0xFF, 0xFF, 0xFF, 0xFF, 0x03,
+    ];
 
     #[test]
     fn parse_hhccjcy01_temperature_reading() {
@@ -1394,4 +3451,1447 @@ mod tests {
         let message = MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_MOISTURE_READING);
         assert!(message.is_ok())
     }
+
+    #[test]
+    fn lock_state_normal_combinations() {
+        assert!(!LockState::from_bytes([0x00]).is_locked());
+        assert!(LockState::from_bytes([0x04]).is_locked());
+        assert!(LockState::from_bytes([0x05]).is_locked());
+        assert!(LockState::from_bytes([0x06]).is_locked());
+        assert!(LockState::from_bytes([0x07]).is_locked());
+    }
+
+    #[test]
+    fn parse_xmznms04lm_lock_reading() {
+        let message = MiBeaconServiceAdvertisement::from_slice(&XMZNMS04LM_LOCK_READING).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(
+            events[0],
+            SensorEvent::BinaryMeasurement {
+                measurement_type: BinaryMeasurementType::Lock,
+                value: true
+            }
+        ));
+        assert!(matches!(
+            events[1],
+            SensorEvent::BinaryMeasurement {
+                measurement_type: BinaryMeasurementType::AntiLock,
+                value: false
+            }
+        ));
+        assert!(matches!(
+            events[2],
+            SensorEvent::BinaryMeasurement {
+                measurement_type: BinaryMeasurementType::ChildLock,
+                value: true
+            }
+        ));
+    }
+
+    #[test]
+    fn lock_event_key_category() {
+        let lock_event = |key_id| LockEvent {
+            operation: LockEventOperation::from_bytes([0x00]),
+            key_id,
+            timestamp: 0,
+        };
+        assert_eq!(
+            lock_event(0x0000_0000).key_category(),
+            LockKeyCategory::LockAdministrator
+        );
+        assert_eq!(
+            lock_event(0xFFFF_FFFF).key_category(),
+            LockKeyCategory::UnknownOperator
+        );
+        assert_eq!(
+            lock_event(0xDEAD_BEEF).key_category(),
+            LockKeyCategory::InvalidOperator
+        );
+        assert_eq!(
+            lock_event(0x0000_0042).key_category(),
+            LockKeyCategory::Bluetooth(0x42)
+        );
+        assert_eq!(
+            lock_event(0x8002_0003).key_category(),
+            LockKeyCategory::Password(3)
+        );
+        assert_eq!(
+            lock_event(0xC0DE_1000).key_category(),
+            LockKeyCategory::Exception(0x1000)
+        );
+        assert_eq!(
+            lock_event(0x8009_0000).key_category(),
+            LockKeyCategory::Unknown(0x8009_0000)
+        );
+    }
+
+    #[test]
+    fn parse_xmznms04lm_lock_event() {
+        let message = MiBeaconServiceAdvertisement::from_slice(&XMZNMS04LM_LOCK_EVENT).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            SensorEvent::DiscreteEvent(DiscreteEvent::Lock {
+                action: LockAction::LockedFromInside,
+                method: LockMethod::Biometrics,
+                key: LockKeyCategory::Fingerprint(2),
+                timestamp: 0x6600_0000,
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_xmznms04lm_fingerprint_admin_match() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&XMZNMS04LM_FINGERPRINT_ADMIN_MATCH).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            SensorEvent::DiscreteEvent(DiscreteEvent::Fingerprint {
+                key_id: FingerprintEventKeyId::LockAdministrator,
+                matching_result: FingerprintEventMatchingResult::MatchingSuccessful,
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_xmznms04lm_fingerprint_low_quality() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&XMZNMS04LM_FINGERPRINT_LOW_QUALITY).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            SensorEvent::DiscreteEvent(DiscreteEvent::Fingerprint {
+                key_id: FingerprintEventKeyId::UnknownOperator,
+                matching_result: FingerprintEventMatchingResult::LowQuality,
+            })
+        ));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn lock_event_timestamp_utc() {
+        use chrono::NaiveDate;
+
+        let message = MiBeaconServiceAdvertisement::from_slice(&XMZNMS04LM_LOCK_EVENT).unwrap();
+        let MiBeaconObjectPayload::LockEvent(event) = &message.objects[0].payload else {
+            panic!("expected lock event");
+        };
+        let expected = NaiveDate::from_ymd_opt(2024, 3, 24)
+            .and_then(|date| date.and_hms_opt(10, 27, 12))
+            .unwrap()
+            .and_utc();
+        assert_eq!(event.timestamp_utc(), expected);
+    }
+
+    #[test]
+    fn connect_event() {
+        let message = MiBeaconServiceAdvertisement::from_slice(&CONNECT_EVENT).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::DiscreteEvent(DiscreteEvent::Connect {
+                object_id: 0x1001
+            })]
+        );
+        assert_eq!(events[0].to_string(), "connect = 0x1001");
+    }
+
+    #[test]
+    fn arming_event() {
+        let message = MiBeaconServiceAdvertisement::from_slice(&ARMING_EVENT).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::DiscreteEvent(DiscreteEvent::Arming {
+                status: ArmingEventStatus::Armed,
+                timestamp: None,
+            })]
+        );
+
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&DISARMING_EVENT_WITH_TIMESTAMP).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::DiscreteEvent(DiscreteEvent::Arming {
+                status: ArmingEventStatus::Disarmed,
+                timestamp: Some(0x6600_0000),
+            })]
+        );
+    }
+
+    #[test]
+    fn doorbell_camera_event() {
+        for (reading, expected) in [
+            (
+                MAOYAN_DOORBELL_RINGING_EVENT,
+                DoorbellCameraEvent::SomeoneIsRingingTheBell,
+            ),
+            (
+                MAOYAN_DOORBELL_DURESS_ALARM_EVENT,
+                DoorbellCameraEvent::DuressAlarm,
+            ),
+        ] {
+            let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+            let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+            assert_eq!(
+                events,
+                vec![SensorEvent::DiscreteEvent(DiscreteEvent::Doorbell {
+                    event_type: expected
+                })]
+            );
+        }
+    }
+
+    #[test]
+    fn weight() {
+        let message = MiBeaconServiceAdvertisement::from_slice(&WEIGHT_READING).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Weight,
+                value: 3.0,
+                unit: UnitOfMeasurement::Kilogram,
+            }]
+        );
+    }
+
+    #[test]
+    fn flower_and_grass_detector_event() {
+        let message = MiBeaconServiceAdvertisement::from_slice(&GCLS002_UNPLUGGED_EVENT).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::BinaryMeasurement {
+                measurement_type: BinaryMeasurementType::PluggedIn,
+                value: false,
+            }]
+        );
+
+        let mut reading = GCLS002_UNPLUGGED_EVENT;
+        reading[15] = 0x00;
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::BinaryMeasurement {
+                measurement_type: BinaryMeasurementType::PluggedIn,
+                value: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn qingping_sensor_location_event() {
+        for (value, expected) in [
+            (0x00, QuingpingSensorLocationEvent::SeparatedFromBase),
+            (0x01, QuingpingSensorLocationEvent::Connected),
+        ] {
+            let mut reading = QINGPING_SENSOR_LOCATION_EVENT;
+            reading[15] = value;
+            let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+            let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+            assert_eq!(
+                events,
+                vec![SensorEvent::DiscreteEvent(DiscreteEvent::SensorLocation {
+                    location: expected
+                })]
+            );
+        }
+    }
+
+    #[test]
+    fn qingping_pomodoro_event() {
+        for (value, expected) in [
+            (0x00, QuingpingPomodoroEvent::Start),
+            (0x01, QuingpingPomodoroEvent::End),
+            (0x02, QuingpingPomodoroEvent::StartOfBreak),
+            (0x03, QuingpingPomodoroEvent::EndOfBreak),
+        ] {
+            let mut reading = QINGPING_POMODORO_EVENT;
+            reading[15] = value;
+            let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+            let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+            assert_eq!(
+                events,
+                vec![SensorEvent::DiscreteEvent(DiscreteEvent::Pomodoro {
+                    phase: expected
+                })]
+            );
+        }
+    }
+
+    #[test]
+    fn dimmer_event() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&YLKG07YL_ROTATE_RIGHT_EVENT).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::DiscreteEvent(DiscreteEvent::Dimmer {
+                action: DimmerAction::RotateRight,
+                value: 2,
+            })]
+        );
+        assert_eq!(events[0].to_string(), "dimmer = rotate_right (2)");
+
+        let mut reading = YLKG07YL_ROTATE_RIGHT_EVENT;
+        for ((key, value, event_type), expected) in [
+            ((0x00, 0xFE, 0x04), (DimmerAction::RotateLeft, 2)),
+            ((0x03, 0x00, 0x04), (DimmerAction::RotateRightPressed, 3)),
+            ((0xFF, 0x00, 0x04), (DimmerAction::RotateLeftPressed, 1)),
+            ((0x00, 0x02, 0x03), (DimmerAction::ShortPress, 2)),
+            ((0x01, 0x05, 0x03), (DimmerAction::LongPress, 5)),
+        ] {
+            reading[15..18].copy_from_slice(&[key, value, event_type]);
+            let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+            let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+            assert_eq!(
+                events,
+                vec![SensorEvent::DiscreteEvent(DiscreteEvent::Dimmer {
+                    action: expected.0,
+                    value: expected.1,
+                })]
+            );
+        }
+
+        // Key events of other devices are not interpreted as dimmer events.
+        reading[2..4].copy_from_slice(&0x0098u16.to_le_bytes());
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        assert_eq!(message.iter_sensor_events().count(), 0);
+    }
+
+    #[test]
+    fn kettle_state() {
+        let message = MiBeaconServiceAdvertisement::from_slice(&YM_K1501_HEATING_READING).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![
+                SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Temperature,
+                    value: 40.0,
+                    unit: UnitOfMeasurement::DegreesCelsius,
+                },
+                SensorEvent::BinaryMeasurement {
+                    measurement_type: BinaryMeasurementType::Power,
+                    value: true,
+                },
+                SensorEvent::DiscreteEvent(DiscreteEvent::Kettle {
+                    state: KettleState::Heating,
+                }),
+            ]
+        );
+        assert_eq!(events[2].to_string(), "kettle = heating");
+
+        let mut reading = YM_K1501_HEATING_READING;
+        for ((power, temperature), expected) in [
+            ((0x00, 25), Some(KettleState::Off)),
+            ((0x01, 99), Some(KettleState::Boiled)),
+            ((0x00, 97), Some(KettleState::Off)),
+            ((0x02, 60), None),
+        ] {
+            reading[15..17].copy_from_slice(&[power, temperature]);
+            let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+            let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+            assert_eq!(
+                events.get(2),
+                expected
+                    .map(|state| SensorEvent::DiscreteEvent(DiscreteEvent::Kettle { state }))
+                    .as_ref()
+            );
+        }
+    }
+
+    #[test]
+    fn remote_button_event() {
+        let message = MiBeaconServiceAdvertisement::from_slice(&YLYK01YL_KEY_EVENT).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::DiscreteEvent(DiscreteEvent::RemoteButton {
+                button: RemoteButton::Plus,
+                event_type: ButtonEventType::DoubleClick,
+            })]
+        );
+        assert_eq!(events[0].to_string(), "remote_button = plus (double_click)");
+
+        // The same key is labeled differently on the fan remote control.
+        let mut reading = YLYK01YL_KEY_EVENT;
+        reading[2..4].copy_from_slice(&0x068Eu16.to_le_bytes());
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::DiscreteEvent(DiscreteEvent::RemoteButton {
+                button: RemoteButton::ColorTemperature,
+                event_type: ButtonEventType::DoubleClick,
+            })]
+        );
+
+        reading[2..4].copy_from_slice(&0x04E6u16.to_le_bytes());
+        reading[15] = 0x00;
+        reading[17] = 0x02;
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::DiscreteEvent(DiscreteEvent::RemoteButton {
+                button: RemoteButton::Swing,
+                event_type: ButtonEventType::LongPress,
+            })]
+        );
+
+        // Unknown key
+        reading[15] = 0x06;
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        assert_eq!(message.iter_sensor_events().count(), 0);
+    }
+
+    #[test]
+    fn toothbrush_event() {
+        let message = MiBeaconServiceAdvertisement::from_slice(&M1S_T500_TOOTHBRUSH_EVENT).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::DiscreteEvent(DiscreteEvent::Toothbrush {
+                event_type: ToothbrushEventType::BrushingStarted,
+                timestamp: None,
+            })]
+        );
+
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&M1S_T500_TOOTHBRUSH_SCORE_EVENT).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![
+                SensorEvent::DiscreteEvent(DiscreteEvent::Toothbrush {
+                    event_type: ToothbrushEventType::BrushingEnded,
+                    timestamp: None,
+                }),
+                SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::ToothbrushScore,
+                    value: 90.0,
+                    unit: UnitOfMeasurement::Unitless,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn xiaobel_toothbrush_event() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&M1S_T500_XIAOBEL_TOOTHBRUSH_EVENT).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::DiscreteEvent(DiscreteEvent::Toothbrush {
+                event_type: ToothbrushEventType::BrushingStarted,
+                timestamp: Some(0x6600_0000),
+            })]
+        );
+
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&M1S_T500_XIAOBEL_TOOTHBRUSH_SCORE_EVENT)
+                .unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![
+                SensorEvent::DiscreteEvent(DiscreteEvent::Toothbrush {
+                    event_type: ToothbrushEventType::BrushingEnded,
+                    timestamp: Some(0x6600_003C),
+                }),
+                SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::ToothbrushScore,
+                    value: 90.0,
+                    unit: UnitOfMeasurement::Unitless,
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn xiaobel_toothbrush_event_timestamp_utc() {
+        use chrono::NaiveDate;
+
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&M1S_T500_XIAOBEL_TOOTHBRUSH_EVENT).unwrap();
+        let Some(SensorEvent::DiscreteEvent(event)) = message.iter_sensor_events().next() else {
+            panic!("expected discrete event");
+        };
+        let expected = NaiveDate::from_ymd_opt(2024, 3, 24)
+            .and_then(|date| date.and_hms_opt(10, 27, 12))
+            .unwrap()
+            .and_utc();
+        assert_eq!(event.timestamp_utc(), Some(expected));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_hhccjcy01_device_type() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_READING).unwrap();
+        let device_type = message.device_type().unwrap();
+        assert_eq!(
+            serde_json::to_value(device_type).unwrap(),
+            serde_json::json!({
+                "name": "Plant Sensor",
+                "model": "HHCCJCY01",
+                "manufacturer": "Xiaomi",
+            })
+        );
+    }
+
+    #[test]
+    fn build_hhccjcy01_temperature_reading() {
+        let bytes = MiBeaconBuilder::new(0x0098)
+            .version(2)
+            .reserved_bits(0b001)
+            .packet_id(0xB1)
+            .mac_address([0x11, 0x22, 0x33, 0x44, 0x55, 0x66])
+            .capabilities(
+                MiBeaconCapabilities::new()
+                    .with_connectable(true)
+                    .with_encryptable(true)
+                    .with_bond_ability(BondAbility::PreBinding),
+            )
+            .object(0x1004, MiBeaconObjectPayload::Temperature(236))
+            .to_bytes();
+        assert_eq!(bytes, HHCCJCY01_TEMPERATURE_READING);
+    }
+
+    #[test]
+    fn write_hhccjcy01_temperature_reading() {
+        use binrw::io::Cursor;
+        use binrw::BinWrite;
+
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_READING).unwrap();
+        let mut writer = Cursor::new(Vec::new());
+        message.write(&mut writer).unwrap();
+        let bytes = writer.into_inner();
+        assert_eq!(bytes, HHCCJCY01_TEMPERATURE_READING);
+
+        let reparsed = MiBeaconServiceAdvertisement::from_slice(&bytes).unwrap();
+        assert_eq!(reparsed.objects.len(), 1);
+        assert_eq!(reparsed.objects[0].id, 0x1004);
+        assert!(matches!(
+            reparsed.objects[0].payload,
+            MiBeaconObjectPayload::Temperature(236)
+        ));
+    }
+
+    #[test]
+    fn sensor_events_with_unhandled() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_RSSI_UNKNOWN_READING)
+                .unwrap();
+        let (events, unhandled) = message.sensor_events_with_unhandled();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Temperature,
+                ..
+            }
+        ));
+        assert_eq!(unhandled.len(), 2);
+        assert!(matches!(unhandled[0], MiBeaconObjectPayload::Rssi(0xC4)));
+        assert!(
+            matches!(unhandled[1], MiBeaconObjectPayload::Unknown(bytes) if bytes == &[0xAA, 0xBB])
+        );
+    }
+
+    #[test]
+    fn formaldehyde_resolution() {
+        let reading = MiBeaconBuilder::new(0x02DF)
+            .object(
+                0x1010,
+                MiBeaconObjectPayload::FormaldehydeConcentration(350),
+            )
+            .object(
+                0x101C,
+                MiBeaconObjectPayload::FormaldehydeConcentrationNew(3500),
+            )
+            .object(0x1004, MiBeaconObjectPayload::Temperature(236))
+            .to_bytes();
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        let readings: Vec<_> = message.iter_readings().collect();
+        let formaldehyde = SensorEvent::NumericMeasurement {
+            measurement_type: NumericMeasurementType::FormaldehydeConcentration,
+            value: 3.5,
+            unit: UnitOfMeasurement::MilligramPerCubicMeter,
+        };
+        assert_eq!(readings[0].event, formaldehyde);
+        assert_eq!(readings[0].resolution, Some(0.01));
+        assert_eq!(readings[1].event, formaldehyde);
+        assert_eq!(readings[1].resolution, Some(0.001));
+        assert_eq!(readings[2].resolution, Some(0.1));
+
+        // Binary measurements and discrete events have no resolution.
+        let message = MiBeaconServiceAdvertisement::from_slice(&YM_K1501_HEATING_READING).unwrap();
+        let resolutions: Vec<_> = message
+            .iter_readings()
+            .map(|reading| reading.resolution)
+            .collect();
+        assert_eq!(resolutions, [Some(1.0), None, None]);
+    }
+
+    #[test]
+    fn lock_battery_level() {
+        let reading = MiBeaconBuilder::new(0x0784)
+            .object(0x4803, MiBeaconObjectPayload::BatteryLevel(87))
+            .to_bytes();
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        assert_eq!(message.device_type().unwrap().model, "XMZNMS04LM");
+        assert_eq!(
+            message.iter_sensor_events().collect::<Vec<_>>(),
+            [SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::BatteryPower,
+                value: 87.0,
+                unit: UnitOfMeasurement::Percent,
+            }]
+        );
+
+        let reading = MiBeaconBuilder::new(0x0784)
+            .object(0x4803, MiBeaconObjectPayload::BatteryLevel(101))
+            .to_bytes();
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        assert!(message.objects[0].payload.is_unknown());
+    }
+
+    #[test]
+    fn event_labels() {
+        assert_eq!(
+            FingerprintEventMatchingResult::MatchingFailed.as_str(),
+            "matching_failed"
+        );
+        assert_eq!(
+            DoorEvent::KnockingOnTheDoor.to_string(),
+            "knocking_on_the_door"
+        );
+        assert_eq!(
+            Gesture::FlipOneHundredEightyDegrees.to_string(),
+            "flip_one_hundred_eighty_degrees"
+        );
+        assert_eq!(ButtonEventType::DoubleClick.as_str(), "double_click");
+        assert_eq!(LockAction::Reserved(0b1001).as_str(), "reserved");
+        assert_eq!(LockKeyCategory::Fingerprint(1).to_string(), "fingerprint");
+        assert_eq!(FingerprintEventKeyId::KeyId(5).as_str(), "key_id");
+        assert_eq!(
+            DimmerAction::RotateLeftPressed.as_str(),
+            "rotate_left_pressed"
+        );
+        assert_eq!(KettleState::Boiled.to_string(), "boiled");
+        assert_eq!(RemoteButton::LightToggle.as_str(), "light_toggle");
+
+        // Discrete events are displayed using the labels.
+        let fingerprint = |key_id| {
+            SensorEvent::DiscreteEvent(DiscreteEvent::Fingerprint {
+                key_id,
+                matching_result: FingerprintEventMatchingResult::LowQuality,
+            })
+            .to_string()
+        };
+        assert_eq!(
+            fingerprint(FingerprintEventKeyId::UnknownOperator),
+            "fingerprint = low_quality (unknown_operator)"
+        );
+        assert_eq!(
+            fingerprint(FingerprintEventKeyId::KeyId(5)),
+            "fingerprint = low_quality (key_id 5)"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn event_labels_match_serialized_names() {
+        for result in [
+            FingerprintEventMatchingResult::MatchingSuccessful,
+            FingerprintEventMatchingResult::SkinTooWet,
+        ] {
+            assert_eq!(
+                serde_json::to_value(result).unwrap(),
+                serde_json::json!(result.as_str())
+            );
+        }
+        assert_eq!(
+            serde_json::to_value(RemoteButton::Timer60Minutes).unwrap(),
+            serde_json::json!(RemoteButton::Timer60Minutes.as_str())
+        );
+        assert_eq!(
+            serde_json::to_value(QuingpingPomodoroEvent::StartOfBreak).unwrap(),
+            serde_json::json!(QuingpingPomodoroEvent::StartOfBreak.as_str())
+        );
+    }
+
+    #[test]
+    fn lenient_header() {
+        // The frame control claims that the MAC address and capabilities are included, but the
+        // objects follow the packet ID directly.
+        let reading = [0x71, 0x20, 0x98, 0x00, 0x01, 0x04, 0x10, 0x02, 0xEC, 0x00];
+        assert!(MiBeaconServiceAdvertisement::from_slice(&reading).is_err());
+        let message = MiBeaconServiceAdvertisement::from_slice_lenient(&reading).unwrap();
+        assert!(!message.frame_control.mac_included());
+        assert!(!message.frame_control.capabilities_included());
+        assert!(message.frame_control.objects_included());
+        assert_eq!(message.mac_address, None);
+        assert_eq!(
+            message.iter_sensor_events().collect::<Vec<_>>(),
+            [SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Temperature,
+                value: 23.6,
+                unit: UnitOfMeasurement::DegreesCelsius,
+            }]
+        );
+
+        // Well-formed frames are parsed as usual.
+        assert_eq!(
+            MiBeaconServiceAdvertisement::from_slice_lenient(&HHCCJCY01_TEMPERATURE_READING)
+                .unwrap(),
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_READING).unwrap()
+        );
+
+        // Unrecoverable frames return the original error.
+        assert!(matches!(
+            MiBeaconServiceAdvertisement::from_slice_lenient(&reading[..9]),
+            Err(ParseError::Truncated { .. })
+        ));
+    }
+
+    #[test]
+    fn try_iter_sensor_events() {
+        let reading = MiBeaconBuilder::new(0x0098)
+            .object(0x1004, MiBeaconObjectPayload::Temperature(236))
+            .object(0x1003, MiBeaconObjectPayload::Rssi(0xC4))
+            .object(0x1006, MiBeaconObjectPayload::Humidity(1500))
+            .object(0x2F00, MiBeaconObjectPayload::Unknown(vec![0xAA, 0xBB]))
+            .to_bytes();
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        let (events, unhandled) = message.try_iter_sensor_events();
+        assert_eq!(
+            events,
+            [SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Temperature,
+                value: 23.6,
+                unit: UnitOfMeasurement::DegreesCelsius,
+            }]
+        );
+        assert_eq!(
+            unhandled,
+            [
+                UnhandledObject {
+                    id: 0x1003,
+                    reason: UnhandledReason::UnsupportedState,
+                },
+                UnhandledObject {
+                    id: 0x1006,
+                    reason: UnhandledReason::OutOfRange,
+                },
+                UnhandledObject {
+                    id: 0x2F00,
+                    reason: UnhandledReason::UnknownId,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn humidity_range() {
+        let reading = MiBeaconBuilder::new(0x0347)
+            .object(0x1006, MiBeaconObjectPayload::Humidity(1000))
+            .to_bytes();
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        assert_eq!(
+            message.iter_sensor_events().collect::<Vec<_>>(),
+            [SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Humidity,
+                value: 100.0,
+                unit: UnitOfMeasurement::Percent,
+            }]
+        );
+
+        let reading = MiBeaconBuilder::new(0x0347)
+            .object(0x1006, MiBeaconObjectPayload::Humidity(1234))
+            .to_bytes();
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        assert!(message.objects[0].payload.is_unknown());
+        assert_eq!(message.iter_sensor_events().count(), 0);
+    }
+
+    #[test]
+    fn illuminance_range() {
+        let illuminance = |lux| {
+            let reading = MiBeaconBuilder::new(0x0098)
+                .object(
+                    0x1007,
+                    MiBeaconObjectPayload::Illuminance(U24::from_u32(lux).unwrap()),
+                )
+                .to_bytes();
+            MiBeaconServiceAdvertisement::from_slice(&reading).unwrap()
+        };
+
+        assert_eq!(
+            illuminance(120_000)
+                .iter_sensor_events()
+                .collect::<Vec<_>>(),
+            [SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Illuminance,
+                value: 120_000.0,
+                unit: UnitOfMeasurement::Lux,
+            }]
+        );
+        for lux in [120_001, U24::MAX] {
+            let message = illuminance(lux);
+            assert!(message.objects[0].payload.is_unknown());
+            assert_eq!(message.iter_sensor_events().count(), 0);
+        }
+    }
+
+    #[test]
+    fn remaining_supplies_range() {
+        let reading = MiBeaconBuilder::new(0x040A)
+            .object(0x1013, MiBeaconObjectPayload::RemainingSupplies(100))
+            .to_bytes();
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        assert_eq!(
+            message.iter_sensor_events().collect::<Vec<_>>(),
+            [SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::RemainingSupplies,
+                value: 100.0,
+                unit: UnitOfMeasurement::Percent,
+            }]
+        );
+
+        let reading = MiBeaconBuilder::new(0x040A)
+            .object(0x1013, MiBeaconObjectPayload::RemainingSupplies(0xFF))
+            .to_bytes();
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        assert!(message.objects[0].payload.is_unknown());
+        assert_eq!(message.iter_sensor_events().count(), 0);
+    }
+
+    #[test]
+    fn temperature_humidity_scaling() {
+        let object = MiBeaconObjectPayload::TemperatureAndHumidity {
+            temperature: 236,
+            humidity: 456,
+        };
+        let events = |device_id| {
+            let reading = MiBeaconBuilder::new(device_id)
+                .object(0x100D, object.clone())
+                .to_bytes();
+            MiBeaconServiceAdvertisement::from_slice(&reading)
+                .unwrap()
+                .iter_sensor_events()
+                .collect::<Vec<_>>()
+        };
+        let expected = |humidity| {
+            [
+                SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Temperature,
+                    value: 23.6,
+                    unit: UnitOfMeasurement::DegreesCelsius,
+                },
+                SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Humidity,
+                    value: humidity,
+                    unit: UnitOfMeasurement::Percent,
+                },
+            ]
+        };
+
+        // The reference parsers do not use a different scale for the LYWSD02, so it reports the
+        // same values as the LYWSDCGQ and LYWSD03MMC.
+        assert_eq!(events(0x045B), expected(45.6));
+        assert_eq!(events(0x01AA), expected(45.6));
+        assert_eq!(events(0x055B), expected(45.6));
+    }
+
+    #[test]
+    fn mesh_advertisement_unsupported() {
+        let reading = MiBeaconBuilder::new(0x0098)
+            .mesh(true)
+            .object(0x1004, MiBeaconObjectPayload::Temperature(236))
+            .to_bytes();
+        assert!(matches!(
+            MiBeaconServiceAdvertisement::from_slice(&reading),
+            Err(ParseError::MeshUnsupported)
+        ));
+        assert!(matches!(
+            MiBeaconServiceAdvertisement::from_slice_lossy(&reading),
+            Err(ParseError::MeshUnsupported)
+        ));
+    }
+
+    #[test]
+    fn protocol_version() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_READING).unwrap();
+        assert_eq!(message.version(), 2);
+        let reading = MiBeaconBuilder::new(0x0098).to_bytes();
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        assert_eq!(message.version(), 5);
+    }
+
+    #[test]
+    fn object_sensor_events_without_allocation() {
+        let lock = MiBeaconObjectPayload::Lock(
+            LockState::new()
+                .with_tongue_ejected(true)
+                .with_child_lock_ejected(true),
+        );
+        let events: Vec<_> = lock.iter_sensor_events(&ParseContext::default()).collect();
+        assert_eq!(events.len(), super::ObjectSensorEvents::CAPACITY);
+        assert_eq!(events, lock.sensor_events());
+
+        let unknown = MiBeaconObjectPayload::Unknown(vec![0xAA]);
+        assert_eq!(
+            unknown.iter_sensor_events(&ParseContext::default()).count(),
+            0
+        );
+
+        for reading in [
+            &HHCCJCY01_TEMPERATURE_READING[..],
+            &CGG1_TEMPERATURE_HUMIDITY_READING,
+            &XMZNMS04LM_LOCK_READING,
+            &M1S_T500_TOOTHBRUSH_SCORE_EVENT,
+        ] {
+            let message = MiBeaconServiceAdvertisement::from_slice(reading).unwrap();
+            let expected: Vec<_> = message
+                .objects
+                .iter()
+                .flat_map(|object| object.payload.to_sensor_events(&message.context()))
+                .collect();
+            assert_eq!(message.iter_sensor_events().collect::<Vec<_>>(), expected);
+            assert_eq!(message.sensor_events_with_unhandled().0, expected);
+        }
+    }
+
+    #[test]
+    fn too_many_objects() {
+        let mut reading = HHCCJCY01_TEMPERATURE_READING.to_vec();
+        for _ in 0..MiBeaconServiceAdvertisement::MAX_OBJECTS {
+            reading.extend_from_slice(&[0xFF, 0xFF, 0x00]);
+        }
+        assert!(matches!(
+            MiBeaconServiceAdvertisement::from_slice(&reading),
+            Err(ParseError::TooManyObjects { limit: 16 })
+        ));
+
+        let (message, skipped) = MiBeaconServiceAdvertisement::from_slice_lossy(&reading).unwrap();
+        assert_eq!(
+            message.objects.len(),
+            MiBeaconServiceAdvertisement::MAX_OBJECTS
+        );
+        assert_eq!(skipped, 3);
+
+        reading.truncate(reading.len() - 3);
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        assert_eq!(
+            message.objects.len(),
+            MiBeaconServiceAdvertisement::MAX_OBJECTS
+        );
+    }
+
+    #[test]
+    fn parse_all_objects() {
+        let objects = MiBeaconObject::parse_all(&[
+            0x04, 0x10, 0x02, 0xEC, 0x00, // Temperature
+            0x06, 0x10, 0x02, 0xC8, 0x01, // Humidity
+        ])
+        .unwrap();
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].id, 0x1004);
+        assert_eq!(objects[0].payload, MiBeaconObjectPayload::Temperature(236));
+        assert_eq!(objects[1].id, 0x1006);
+        assert_eq!(objects[1].payload, MiBeaconObjectPayload::Humidity(456));
+
+        assert_eq!(MiBeaconObject::parse_all(&[]).unwrap(), []);
+        assert!(matches!(
+            MiBeaconObject::parse_all(&[0x04, 0x10, 0x02, 0xEC]),
+            Err(ParseError::ObjectLengthExceedsBuffer {
+                object_id: 0x1004,
+                length: 2,
+                remaining: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn read_from_cursor() {
+        let mut buffer = vec![0xAA, 0xBB, 0xCC];
+        buffer.extend_from_slice(&HHCCJCY01_TEMPERATURE_READING);
+        let mut cursor = Cursor::new(&buffer);
+        cursor.set_position(3);
+        let message = MiBeaconServiceAdvertisement::read_from(&mut cursor).unwrap();
+        assert_eq!(
+            message,
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_READING).unwrap()
+        );
+        assert_eq!(cursor.position(), buffer.len() as u64);
+
+        let mut cursor = Cursor::new(&buffer);
+        cursor.set_position(buffer.len() as u64 - 1);
+        assert!(matches!(
+            MiBeaconServiceAdvertisement::read_from(&mut cursor),
+            Err(ParseError::Truncated {
+                expected: None,
+                found: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn read_back_to_back_frames() {
+        let mut buffer = HHCCJCY01_TEMPERATURE_READING.to_vec();
+        buffer.extend_from_slice(&CGG1_TEMPERATURE_HUMIDITY_READING);
+        let mut cursor = Cursor::new(&buffer);
+
+        let first = MiBeaconServiceAdvertisement::read_from_bounded(
+            &mut cursor,
+            HHCCJCY01_TEMPERATURE_READING.len(),
+        )
+        .unwrap();
+        assert_eq!(
+            first,
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_READING).unwrap()
+        );
+        assert_eq!(
+            cursor.position(),
+            HHCCJCY01_TEMPERATURE_READING.len() as u64
+        );
+
+        let second = MiBeaconServiceAdvertisement::read_from_bounded(
+            &mut cursor,
+            CGG1_TEMPERATURE_HUMIDITY_READING.len(),
+        )
+        .unwrap();
+        assert_eq!(
+            second,
+            MiBeaconServiceAdvertisement::from_slice(&CGG1_TEMPERATURE_HUMIDITY_READING).unwrap()
+        );
+        assert_eq!(cursor.position(), buffer.len() as u64);
+
+        // The frame length must not exceed the remaining data.
+        let mut cursor = Cursor::new(&HHCCJCY01_TEMPERATURE_READING);
+        assert!(matches!(
+            MiBeaconServiceAdvertisement::read_from_bounded(
+                &mut cursor,
+                HHCCJCY01_TEMPERATURE_READING.len() + 1
+            ),
+            Err(ParseError::Truncated {
+                expected: Some(18),
+                found: 17
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_context() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_READING).unwrap();
+        let ctx = message.context();
+        assert_eq!(
+            ctx,
+            ParseContext {
+                device_id: 0x0098,
+                packet_id: 0xB1,
+                mac: Some(MacAddress::from([0x11, 0x22, 0x33, 0x44, 0x55, 0x66])),
+            }
+        );
+
+        // Key events can only be decoded if the device is known.
+        let payload = MiBeaconObjectPayload::KeyEvent {
+            key: 0x00,
+            value: 0x02,
+            event_type: 0x04,
+        };
+        assert!(payload.sensor_events().is_empty());
+        let ctx = ParseContext {
+            device_id: super::DIMMER_DEVICE_ID,
+            ..ctx
+        };
+        assert_eq!(
+            payload.to_sensor_events(&ctx),
+            [SensorEvent::DiscreteEvent(DiscreteEvent::Dimmer {
+                action: DimmerAction::RotateRight,
+                value: 2,
+            })]
+        );
+    }
+
+    #[test]
+    fn parse_cgg1_temperature_humidity_reading() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&CGG1_TEMPERATURE_HUMIDITY_READING).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events[0],
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Temperature,
+                value,
+                ..
+            } if value == 22.5
+        ));
+        assert!(matches!(
+            events[1],
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Humidity,
+                value,
+                ..
+            } if value == 39.4
+        ));
+    }
+
+    #[test]
+    fn enumerate_device_types() {
+        assert!(all_device_types().count() > 0);
+        assert!(all_device_types().any(
+            |(device_id, device_type)| device_id == 0x055B && device_type.model == "LYWSD03MMC"
+        ));
+    }
+
+    #[test]
+    fn encrypted_device_ids() {
+        assert!(requires_bindkey(0x0B48));
+        assert!(requires_bindkey(0x055B));
+        assert!(!requires_bindkey(0x0098));
+        assert!(super::ENCRYPTED_DEVICE_IDS
+            .iter()
+            .all(|device_id| super::DEVICE_TYPES.contains_key(device_id)));
+    }
+
+    #[test]
+    fn mac_address_string_round_trip() {
+        let mac_address = MacAddress([0xA4, 0xC1, 0x38, 0x0B, 0x2C, 0xEF]);
+        assert_eq!(mac_address.to_string(), "A4:C1:38:0B:2C:EF");
+        assert_eq!("A4:C1:38:0B:2C:EF".parse::<MacAddress>(), Ok(mac_address));
+        assert_eq!(
+            "a4-c1-38-0b-2c-ef"
+                .parse::<MacAddress>()
+                .unwrap()
+                .to_string(),
+            "A4:C1:38:0B:2C:EF"
+        );
+        assert_eq!(
+            "A4:C1:38:0B:2C".parse::<MacAddress>(),
+            Err(MacAddressParseError)
+        );
+        assert_eq!(
+            "A4:C1:38:0B:2C:EF:00".parse::<MacAddress>(),
+            Err(MacAddressParseError)
+        );
+        assert_eq!(
+            "A4:C1:38:0B:2C:XY".parse::<MacAddress>(),
+            Err(MacAddressParseError)
+        );
+        assert_eq!(
+            "A4:C1:38:0B:2C:+F".parse::<MacAddress>(),
+            Err(MacAddressParseError)
+        );
+    }
+
+    #[cfg(feature = "bluetooth")]
+    #[test]
+    fn mac_address_from_btleplug() {
+        use btleplug::api::BDAddr;
+
+        let address = BDAddr::from([0xA4, 0xC1, 0x38, 0x0B, 0x2C, 0xEF]);
+        let mac_address = MacAddress::from(address);
+        assert_eq!(mac_address.to_string(), address.to_string());
+        assert_eq!(
+            mac_address,
+            "A4:C1:38:0B:2C:EF".parse::<MacAddress>().unwrap()
+        );
+        assert_eq!(BDAddr::from(mac_address), address);
+    }
+
+    #[cfg(feature = "bluetooth")]
+    #[test]
+    fn mac_address_matches_btleplug_address() {
+        use btleplug::api::BDAddr;
+
+        // The MAC address is transmitted in reverse order (66:55:44:33:22:11).
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_READING).unwrap();
+        let address = BDAddr::from([0x11, 0x22, 0x33, 0x44, 0x55, 0x66]);
+        assert_eq!(message.mac_address, Some(MacAddress::from(address)));
+        assert_eq!(
+            message.mac_address.unwrap().to_string(),
+            address.to_string()
+        );
+    }
+
+    #[test]
+    fn mac_address_byte_conversions() {
+        let bytes = [0xA4, 0xC1, 0x38, 0x0B, 0x2C, 0xEF];
+        let mac_address = MacAddress::from(bytes);
+        assert_eq!(mac_address.as_slice(), &bytes);
+        assert_eq!(mac_address, bytes);
+        assert_eq!(bytes, mac_address);
+        assert_ne!(mac_address, [0xA4, 0xC1, 0x38, 0x0B, 0x2C, 0x00]);
+        assert_eq!(<[u8; 6]>::from(mac_address), bytes);
+        assert_eq!(
+            MacAddress::try_from(&bytes[..]),
+            Ok(MacAddress::from(bytes))
+        );
+        assert_eq!(MacAddress::try_from(&bytes[..5]), Err(MacAddressParseError));
+        assert_eq!(
+            MacAddress::try_from(&[0u8; 7][..]),
+            Err(MacAddressParseError)
+        );
+    }
+
+    #[test]
+    fn sequence_tracker_wraparound() {
+        let device = MacAddress::from([0x66, 0x55, 0x44, 0x33, 0x22, 0x11]);
+        let other_device = MacAddress::from([0xA4, 0xC1, 0x38, 0x0B, 0x2C, 0xEF]);
+        let mut tracker = SequenceTracker::new();
+
+        assert!(tracker.is_new(device, 254));
+        assert!(!tracker.is_new(device, 254));
+        assert!(tracker.is_new(device, 255));
+        assert!(tracker.is_new(device, 0));
+        assert!(!tracker.is_new(device, 0));
+        assert!(tracker.is_new(device, 1));
+
+        // Packet IDs are tracked per device.
+        assert!(tracker.is_new(other_device, 1));
+        assert!(!tracker.is_new(device, 1));
+    }
+
+    #[test]
+    fn deduplicate_repeated_packets() {
+        let first =
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_READING).unwrap();
+        let mut reading = HHCCJCY01_TEMPERATURE_READING;
+        reading[4] = 0xB2;
+        let second = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+
+        let mut deduplicator = Deduplicator::new();
+        let packets = [first.clone(), first.clone(), first.clone(), second.clone()];
+        let packet_ids: Vec<u8> = deduplicator
+            .filter(&packets)
+            .map(|advertisement| advertisement.packet_id)
+            .collect();
+        assert_eq!(packet_ids, vec![0xB1, 0xB2]);
+
+        // Without a MAC address, the device ID is used instead.
+        let mut without_mac = second.clone();
+        without_mac.mac_address = None;
+        assert!(deduplicator.is_new(&without_mac));
+        assert!(!deduplicator.is_new(&without_mac));
+        assert!(!deduplicator.is_new(&second));
+
+        let mut deduplicator = Deduplicator::with_window(2);
+        let packet_ids: Vec<u8> = deduplicator
+            .filter(packets)
+            .map(|advertisement| advertisement.packet_id)
+            .collect();
+        assert_eq!(packet_ids, vec![0xB1, 0xB1, 0xB2]);
+    }
+
+    #[test]
+    fn capabilities_with_io() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&MIBEACON_CAPABILITIES_WITH_IO).unwrap();
+        assert_eq!(
+            message.capabilities_summary(),
+            Some(Capabilities {
+                connectable: true,
+                centralable: false,
+                encryptable: true,
+                bond_ability: BondAbility::PreBinding,
+                io: true,
+            })
+        );
+        let io_capabilities = message.io_capabilities.unwrap().base_io_capabilities;
+        assert!(io_capabilities.can_input_6_digits());
+        assert!(io_capabilities.can_output_6_digits());
+        assert!(!io_capabilities.can_read_qr_codes());
+        assert!(message.objects.is_empty());
+    }
+
+    #[test]
+    fn frame_control_auth_mode_and_version() {
+        let frame_control = FrameControl::from_bytes([0x58, 0x59]);
+        assert!(frame_control.is_encrypted());
+        assert!(frame_control.registered());
+        assert_eq!(frame_control.auth_mode(), AuthMode::Standard);
+        assert_eq!(frame_control.version(), 5);
+
+        let frame_control = FrameControl::from_bytes([0x71, 0x20]);
+        assert_eq!(frame_control.auth_mode(), AuthMode::OldVersion);
+        assert_eq!(frame_control.version(), 2);
+    }
+
+    #[test]
+    fn truncated_frame() {
+        for length in [4, 13, 14] {
+            let result =
+                MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_READING[..length]);
+            assert!(
+                matches!(
+                    result,
+                    Err(ParseError::Truncated {
+                        expected: None,
+                        found,
+                    }) if found == length
+                ),
+                "{result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn object_length_exceeds_buffer() {
+        let mut reading = HHCCJCY01_TEMPERATURE_READING;
+        reading[14] = 4;
+        assert!(matches!(
+            MiBeaconServiceAdvertisement::from_slice(&reading),
+            Err(ParseError::ObjectLengthExceedsBuffer {
+                object_id: 0x1004,
+                length: 4,
+                remaining: 2,
+            })
+        ));
+        assert!(matches!(
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_READING[..16]),
+            Err(ParseError::ObjectLengthExceedsBuffer {
+                object_id: 0x1004,
+                length: 2,
+                remaining: 1,
+            })
+        ));
+    }
+
+    #[test]
+    fn lossy_parsing_stops_at_garbage() {
+        let mut reading = HHCCJCY01_TEMPERATURE_READING.to_vec();
+        reading.extend_from_slice(&[0xFF, 0xFF, 0x09, 0x01]);
+
+        assert!(MiBeaconServiceAdvertisement::from_slice(&reading).is_err());
+
+        let (advertisement, skipped) =
+            MiBeaconServiceAdvertisement::from_slice_lossy(&reading).unwrap();
+        assert_eq!(skipped, 4);
+        assert_eq!(advertisement.objects.len(), 1);
+        assert!(matches!(
+            advertisement.objects[0].payload,
+            MiBeaconObjectPayload::Temperature(236)
+        ));
+
+        let (advertisement, skipped) =
+            MiBeaconServiceAdvertisement::from_slice_lossy(&HHCCJCY01_TEMPERATURE_READING).unwrap();
+        assert_eq!(skipped, 0);
+        assert_eq!(advertisement.objects.len(), 1);
+    }
+
+    #[test]
+    fn iter_objects() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_RSSI_UNKNOWN_READING)
+                .unwrap();
+        let objects: Vec<_> = message.iter_objects().collect();
+        assert_eq!(objects.len(), 3);
+        assert!(matches!(
+            objects[0],
+            (0x1004, 2, MiBeaconObjectPayload::Temperature(_))
+        ));
+        assert!(!objects[0].2.is_unknown());
+        assert!(!objects[1].2.is_unknown());
+        assert!(matches!(objects[2], (0x2F00, 2, payload) if payload.is_unknown()));
+    }
+
+    #[test]
+    fn raw_payload_bytes() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_RSSI_UNKNOWN_READING)
+                .unwrap();
+        assert_eq!(
+            message.objects[2].raw_payload_bytes(),
+            Some([0xAA, 0xBB].as_slice())
+        );
+        let temperature_bytes = message.objects[0].raw_payload_bytes();
+        if cfg!(feature = "raw-payload") {
+            assert_eq!(temperature_bytes, Some([0xEC, 0x00].as_slice()));
+        } else {
+            assert_eq!(temperature_bytes, None);
+        }
+    }
+
+    #[test]
+    fn construct_object() {
+        use binrw::io::Cursor;
+        use binrw::BinWrite;
+
+        let object = MiBeaconObject::new(0x1004, MiBeaconObjectPayload::Temperature(236));
+        assert_eq!(object.length, 2);
+        if cfg!(feature = "raw-payload") {
+            assert_eq!(object.raw_payload_bytes(), Some([0xEC, 0x00].as_slice()));
+        } else {
+            assert_eq!(object.raw_payload_bytes(), None);
+        }
+
+        let mut writer = Cursor::new(Vec::new());
+        object.write(&mut writer).unwrap();
+        let bytes = writer.into_inner();
+        assert_eq!(bytes, [0x04, 0x10, 0x02, 0xEC, 0x00]);
+
+        // Parsed objects are equal to constructed ones, regardless of the stored raw payload.
+        let parsed = MiBeaconObject::parse_all(&bytes).unwrap();
+        assert_eq!(parsed, [object]);
+    }
+
+    #[test]
+    fn known_object_ids() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&HHCCJCY01_TEMPERATURE_RSSI_UNKNOWN_READING)
+                .unwrap();
+        let (id, _, payload) = message.iter_objects().nth(2).unwrap();
+        assert!(payload.is_unknown());
+        assert!(!MiBeaconObjectPayload::is_known_id(id));
+
+        let mut reading = HHCCJCY01_TEMPERATURE_RSSI_UNKNOWN_READING;
+        reading[21..23].copy_from_slice(&0x4804u16.to_le_bytes());
+        let message = MiBeaconServiceAdvertisement::from_slice(&reading).unwrap();
+        let (id, _, payload) = message.iter_objects().nth(2).unwrap();
+        assert!(payload.is_unknown());
+        assert!(MiBeaconObjectPayload::is_known_id(id));
+
+        assert!(MiBeaconObjectPayload::is_known_id(0x1004));
+    }
+
+    #[test]
+    fn sensor_event_equality() {
+        let message =
+            MiBeaconServiceAdvertisement::from_slice(&CGG1_TEMPERATURE_HUMIDITY_READING).unwrap();
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![
+                SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Temperature,
+                    value: 22.5,
+                    unit: UnitOfMeasurement::DegreesCelsius,
+                },
+                SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Humidity,
+                    value: 39.4,
+                    unit: UnitOfMeasurement::Percent,
+                },
+            ]
+        );
+    }
 }
@@ -17,27 +17,70 @@
 #![allow(clippy::must_use_candidate)]
 
 use crate::device::DeviceType;
-use crate::sensor::{NumericMeasurementType, SensorEvent, UnitOfMeasurement};
+use crate::sensor::{NumericMeasurementType, Reading, SensorEvent, UnitOfMeasurement};
 use crate::ParseError;
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use binrw::io::Cursor;
 use binrw::{binread, BinRead};
 use modular_bitfield::prelude::*;
 use phf::phf_map;
-use std::io::Cursor;
 
-static DEVICE_TYPES: phf::Map<u16, DeviceType> = phf_map! {
-    0x181Du16 => DeviceType { name: "Mi Smart Scale", model: "XMTZC01HM/XMTZC04HM", manufacturer: "Xiaomi" },
-    0x181Bu16 => DeviceType { name: "Mi Body Composition Scale", model: "XMTZC02HM/XMTZC05HM/NUN4049CN", manufacturer: "Xiaomi" },
+pub(crate) static DEVICE_TYPES: phf::Map<u16, DeviceType> = phf_map! {
+    0x181Du16 => DeviceType { name: Cow::Borrowed("Mi Smart Scale"), model: Cow::Borrowed("XMTZC01HM/XMTZC04HM"), manufacturer: Cow::Borrowed("Xiaomi") },
+    0x181Bu16 => DeviceType { name: Cow::Borrowed("Mi Body Composition Scale"), model: Cow::Borrowed("XMTZC02HM/XMTZC05HM/NUN4049CN"), manufacturer: Cow::Borrowed("Xiaomi") },
 };
 
-enum WeightUnit {
+/// Iterate over all known Mi Scale device types and their device IDs.
+pub fn all_device_types() -> impl Iterator<Item = (u16, &'static DeviceType)> {
+    DEVICE_TYPES
+        .entries()
+        .map(|(device_id, device_type)| (*device_id, device_type))
+}
+
+/// Maximum capacity of the supported scales (in kilograms).
+///
+/// Weights above this value are considered implausible and are not reported.
+pub const MAX_WEIGHT_KILOGRAMS: f64 = 150.0;
+
+/// Unit (and resolution) of the raw 16-bit weight value (see [`PacketHeaderV1::unit`] and
+/// [`PacketHeaderV2::unit`]).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum WeightUnit {
+    /// 0.01 lb per step, the maximum representable weight is 655.35 lb.
     OneHundredPounds,
+    /// 0.01 jin per step, the maximum representable weight is 655.35 jin.
     OneHundredCatty,
+    /// 0.005 kg per step, the maximum representable weight is 327.675 kg.
     TwoHundredKilograms,
 }
 
+impl WeightUnit {
+    /// Get the unit from the header flags, or [`None`] if both pounds and catty are set.
+    fn from_flags(is_pounds: bool, is_catty: bool) -> Option<Self> {
+        match (is_pounds, is_catty) {
+            (true, true) => None,
+            (true, false) => Some(Self::OneHundredPounds),
+            (false, true) => Some(Self::OneHundredCatty),
+            (false, false) => Some(Self::TwoHundredKilograms),
+        }
+    }
+
+    /// Convert a raw weight value (as contained in the packet) in this unit to kilograms.
+    ///
+    /// Note that one catty (jin) is 0.5 kg.
+    pub fn to_kilograms(self, weight: u16) -> f64 {
+        match self {
+            Self::TwoHundredKilograms => f64::from(weight) * 0.005,
+            Self::OneHundredPounds => f64::from(weight) * 0.0045359237,
+            Self::OneHundredCatty => f64::from(weight) * 0.005,
+        }
+    }
+}
+
 /// Mi Scale Packet Header (v1 protocol variant)
 #[bitfield]
-#[derive(BinRead, Debug)]
+#[derive(BinRead, Clone, Copy, PartialEq, Eq, Debug)]
 #[br(map = Self::from_bytes)]
 pub struct PacketHeaderV1 {
     /// This is [`true`] if the weight unit is pounds (lbs).
@@ -57,7 +100,7 @@ pub struct PacketHeaderV1 {
 /// Mi Scale Packet (v1 protocol variant)
 #[binread]
 #[br(little)]
-#[derive(Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct PacketV1 {
     /// Packet Header
     pub header: PacketHeaderV1,
@@ -67,28 +110,35 @@ pub struct PacketV1 {
     reserved: [u8; 7],
 }
 
+impl PacketHeaderV1 {
+    /// Get the unit of the weight value, or [`None`] if the unit flags are inconsistent (i.e. both
+    /// pounds and catty are set).
+    pub fn unit(&self) -> Option<WeightUnit> {
+        WeightUnit::from_flags(self.weight_unit_is_pounds(), self.weight_unit_is_catty())
+    }
+
+    /// Returns `true` if the packet contains a weight measurement.
+    pub fn weight_present(&self) -> bool {
+        !self.weight_removed()
+    }
+}
+
 impl PacketV1 {
+    /// Length of the packet (in bytes).
+    pub const LENGTH: usize = 10;
+
     /// Get weight and unit from the packet (if present).
     fn weight(&self) -> Option<(u16, WeightUnit)> {
-        if self.header.weight_removed() {
+        if !self.header.weight_present() {
             return None;
         }
-
-        let unit = if self.header.weight_unit_is_pounds() {
-            WeightUnit::OneHundredPounds
-        } else if self.header.weight_unit_is_catty() {
-            WeightUnit::OneHundredCatty
-        } else {
-            WeightUnit::TwoHundredKilograms
-        };
-
-        Some((self.weight, unit))
+        Some((self.weight, self.header.unit()?))
     }
 }
 
 /// Mi Scale Packet Header (v2 protocol variant)
 #[bitfield]
-#[derive(BinRead, Debug)]
+#[derive(BinRead, Clone, Copy, PartialEq, Eq, Debug)]
 #[br(map = Self::from_bytes)]
 pub struct PacketHeaderV2 {
     #[allow(dead_code)]
@@ -107,44 +157,90 @@ pub struct PacketHeaderV2 {
     reserved3: bool,
 }
 
+/// Date and time of a measurement, as set on the scale's clock (without time zone).
+#[binread]
+#[br(little)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MiScaleTimestamp {
+    /// Year (e.g. 2024).
+    pub year: u16,
+    /// Month (1-12).
+    pub month: u8,
+    /// Day of the month (1-31).
+    pub day: u8,
+    /// Hour (0-23).
+    pub hour: u8,
+    /// Minute (0-59).
+    pub minute: u8,
+    /// Second (0-59).
+    pub second: u8,
+}
+
+impl MiScaleTimestamp {
+    /// Get the timestamp as [`chrono::NaiveDateTime`].
+    ///
+    /// Returns [`None`] if the fields do not form a valid date and time.
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn to_naive_datetime(&self) -> Option<chrono::NaiveDateTime> {
+        chrono::NaiveDate::from_ymd_opt(
+            i32::from(self.year),
+            u32::from(self.month),
+            u32::from(self.day),
+        )?
+        .and_hms_opt(
+            u32::from(self.hour),
+            u32::from(self.minute),
+            u32::from(self.second),
+        )
+    }
+}
+
 /// Mi Scale Packet (v2 protocol variant)
 #[binread]
 #[br(little)]
-#[derive(Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct PacketV2 {
     /// Packet Header
     pub header: PacketHeaderV2,
-    #[allow(dead_code)]
-    reserved: [u8; 7],
+    /// Date and time of the measurement.
+    pub timestamp: MiScaleTimestamp,
     /// Measured body impedance.
     pub impedance: u16,
     /// Measured weight.
     pub weight: u16,
 }
 
+impl PacketHeaderV2 {
+    /// Get the unit of the weight value, or [`None`] if the unit flags are inconsistent (i.e. both
+    /// pounds and catty are set).
+    pub fn unit(&self) -> Option<WeightUnit> {
+        WeightUnit::from_flags(self.weight_unit_is_pounds(), self.weight_unit_is_catty())
+    }
+
+    /// Returns `true` if the packet contains a weight measurement.
+    pub fn weight_present(&self) -> bool {
+        !self.weight_removed()
+    }
+}
+
 impl PacketV2 {
+    /// Length of the packet (in bytes).
+    pub const LENGTH: usize = 13;
+
     /// Get weight and unit from the packet (if present).
     fn weight(&self) -> Option<(u16, WeightUnit)> {
-        if self.header.weight_removed() {
+        if !self.header.weight_present() {
             return None;
         }
-
-        let unit = if self.header.weight_unit_is_pounds() {
-            WeightUnit::OneHundredPounds
-        } else if self.header.weight_unit_is_catty() {
-            WeightUnit::OneHundredCatty
-        } else {
-            WeightUnit::TwoHundredKilograms
-        };
-
-        Some((self.weight, unit))
+        Some((self.weight, self.header.unit()?))
     }
 }
 
 /// Mi Scale Packet (either v1 or v2 protocol variant).
 #[binread]
 #[br(little)]
-#[derive(Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 #[br(import(device_id: u16))]
 pub enum MiScalePacket {
     /// This is a v1 protocol packet.
@@ -164,28 +260,72 @@ impl MiScalePacket {
         }
     }
 
+    /// Returns `true` if the weight measurement is stabilized (i.e. the final value).
+    pub fn weight_stabilized(&self) -> bool {
+        match &self {
+            Self::MiScaleV1(payload) => payload.header.weight_stabilized(),
+            Self::MiScaleV2(payload) => payload.header.weight_stabilized(),
+        }
+    }
+
+    /// Returns `true` if the body impedance measurement is stabilized (v2 only).
+    pub fn impedance_stabilized(&self) -> bool {
+        match &self {
+            Self::MiScaleV1(_) => false,
+            Self::MiScaleV2(payload) => payload.header.impedance_stabilized(),
+        }
+    }
+
     /// Get the weight and unit from the packet (may be omitted in v2).
+    ///
+    /// Returns [`None`] if the unit flags are inconsistent or the weight exceeds
+    /// [`MAX_WEIGHT_KILOGRAMS`].
     fn weight(&self) -> Option<(u16, WeightUnit)> {
-        match &self {
+        let (weight, unit) = match &self {
             Self::MiScaleV1(payload) => payload.weight(),
             Self::MiScaleV2(payload) => payload.weight(),
+        }?;
+        if unit.to_kilograms(weight) > MAX_WEIGHT_KILOGRAMS {
+            return None;
         }
+        Some((weight, unit))
     }
 
     /// Get the weight from the packet (normalized to kg, may be omitted in v2).
     fn weight_kilograms(&self) -> Option<f64> {
+        self.weight()
+            .map(|(weight, unit)| unit.to_kilograms(weight))
+    }
+
+    /// Get the weight from the packet in the unit that the scale is configured to (may be omitted
+    /// in v2).
+    ///
+    /// Implausible weights (i.e. with both pounds and catty unit flags set or above
+    /// [`MAX_WEIGHT_KILOGRAMS`]) are omitted as well.
+    pub fn weight_with_unit(&self) -> Option<(f64, UnitOfMeasurement)> {
         self.weight().map(|(weight, unit)| match unit {
-            WeightUnit::TwoHundredKilograms => f64::from(weight) * 0.005,
-            WeightUnit::OneHundredPounds => f64::from(weight) * 0.0045359237,
-            WeightUnit::OneHundredCatty => f64::from(weight) * 0.01,
+            WeightUnit::TwoHundredKilograms => {
+                (f64::from(weight) / 200.0, UnitOfMeasurement::Kilogram)
+            }
+            WeightUnit::OneHundredPounds => (f64::from(weight) / 100.0, UnitOfMeasurement::Pound),
+            WeightUnit::OneHundredCatty => (f64::from(weight) / 100.0, UnitOfMeasurement::Catty),
         })
     }
+
+    /// Get the date and time of the measurement from the packet (v2 only).
+    #[must_use]
+    pub fn timestamp(&self) -> Option<MiScaleTimestamp> {
+        match &self {
+            MiScalePacket::MiScaleV1(_) => None,
+            MiScalePacket::MiScaleV2(packet) => Some(packet.timestamp),
+        }
+    }
 }
 
 /// Service Advertisement in the HHCCJCY10 Plant Sensor (Pink Version) format.
 #[binread]
 #[br(little)]
-#[derive(Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct MiScaleServiceAdvertisement {
     #[allow(dead_code)]
     reserved: u16,
@@ -197,9 +337,36 @@ pub struct MiScaleServiceAdvertisement {
 }
 
 impl MiScaleServiceAdvertisement {
+    /// Length of the fields preceding the packet (in bytes).
+    const HEADER_LENGTH: usize = 4;
+
     /// Parses a [MiScaleServiceAdvertisement] from a byte slice.
+    ///
+    /// Returns [`ParseError::Truncated`] if the slice is too short for the packet and
+    /// [`ParseError::UnknownDeviceId`] if the device ID does not belong to a known scale.
     pub fn from_slice(slice: &[u8]) -> Result<Self, ParseError> {
-        Ok(Self::read(&mut Cursor::new(slice))?)
+        let Some(&[device_id_lo, device_id_hi]) = slice.get(2..Self::HEADER_LENGTH) else {
+            return Err(ParseError::Truncated {
+                expected: Some(Self::HEADER_LENGTH),
+                found: slice.len(),
+            });
+        };
+        let device_id = u16::from_le_bytes([device_id_lo, device_id_hi]);
+        let packet_length = match device_id {
+            0x181D => PacketV1::LENGTH,
+            0x181B => PacketV2::LENGTH,
+            _ => return Err(ParseError::UnknownDeviceId { device_id }),
+        };
+        let expected = Self::HEADER_LENGTH + packet_length;
+        if slice.len() < expected {
+            return Err(ParseError::Truncated {
+                expected: Some(expected),
+                found: slice.len(),
+            });
+        }
+
+        Self::read(&mut Cursor::new(slice))
+            .map_err(|err| ParseError::from_binrw(err, slice.len(), Some(expected)))
     }
 
     /// Get device type of advertisement sender.
@@ -208,23 +375,566 @@ impl MiScaleServiceAdvertisement {
         DEVICE_TYPES.get(&self.device_id)
     }
 
+    /// Get the measured weight in the unit that the scale is configured to (if present).
+    ///
+    /// The weight event yielded by [`Self::iter_sensor_events`] is always normalized to kilograms.
+    pub fn weight_with_unit(&self) -> Option<(f64, UnitOfMeasurement)> {
+        self.payload.weight_with_unit()
+    }
+
+    /// Get the date and time of the measurement as set on the scale's clock (v2 only).
+    #[must_use]
+    pub fn timestamp(&self) -> Option<MiScaleTimestamp> {
+        self.payload.timestamp()
+    }
+
     /// Yields a list of sensor events parsed from the objects contained in the service advertisement.
+    ///
+    /// Weight and impedance are yielded even if the measurement is not stabilized yet (i.e. while
+    /// the value is still fluctuating), use [`MiScalePacket::weight_stabilized`] and
+    /// [`MiScalePacket::impedance_stabilized`] to check, [`Self::iter_readings`] to get the
+    /// stability together with each event or [`Self::iter_stabilized_sensor_events`] to skip such
+    /// readings.
     pub fn iter_sensor_events(&self) -> impl Iterator<Item = SensorEvent> + '_ {
-        let mut events = Vec::with_capacity(2);
+        self.readings().into_iter().map(|reading| reading.event)
+    }
+
+    /// Yields a list of sensor events parsed from the objects contained in the service
+    /// advertisement, but skips measurements that are not stabilized yet.
+    pub fn iter_stabilized_sensor_events(&self) -> impl Iterator<Item = SensorEvent> + '_ {
+        self.readings()
+            .into_iter()
+            .filter(|reading| reading.is_final == Some(true))
+            .map(|reading| reading.event)
+    }
+
+    /// Yields the sensor events together with the information whether the measurement is final
+    /// (see [`Reading::is_final`]), e.g. to show the weight as "in progress" while it is still
+    /// fluctuating.
+    pub fn iter_readings(&self) -> impl Iterator<Item = Reading> + '_ {
+        self.readings().into_iter()
+    }
+
+    fn readings(&self) -> Vec<Reading> {
+        let mut readings = Vec::with_capacity(2);
         if let Some(weight) = self.payload.weight_kilograms() {
-            events.push(SensorEvent::NumericMeasurement {
-                measurement_type: NumericMeasurementType::Weight,
-                value: weight,
-                unit: UnitOfMeasurement::Kilogram,
+            readings.push(Reading {
+                event: SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Weight,
+                    value: weight,
+                    unit: UnitOfMeasurement::Kilogram,
+                },
+                source_object_id: None,
+                is_final: Some(self.payload.weight_stabilized()),
+                resolution: None,
             });
         }
         if let Some(impedance) = self.payload.impedance() {
-            events.push(SensorEvent::NumericMeasurement {
-                measurement_type: NumericMeasurementType::Impedance,
-                value: f64::from(impedance),
-                unit: UnitOfMeasurement::Ohm,
+            readings.push(Reading {
+                event: SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Impedance,
+                    value: f64::from(impedance),
+                    unit: UnitOfMeasurement::Ohm,
+                },
+                source_object_id: None,
+                is_final: Some(self.payload.impedance_stabilized()),
+                resolution: None,
             });
         }
-        events.into_iter()
+        readings
+    }
+}
+
+/// Biological sex of a person (used for calculating body composition metrics).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Sex {
+    /// Male
+    Male,
+    /// Female
+    Female,
+}
+
+/// Profile of the person on the scale (used for calculating body composition metrics).
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Profile {
+    /// Age (years)
+    pub age: u8,
+    /// Height (cm)
+    pub height_cm: f64,
+    /// Biological sex
+    pub sex: Sex,
+}
+
+/// Body composition metrics derived from weight and body impedance.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BodyMetrics {
+    /// Body Mass Index (kg/m²)
+    pub bmi: f64,
+    /// Body Fat (%)
+    pub body_fat_percent: f64,
+    /// Body Water (%)
+    pub water_percent: f64,
+    /// Muscle Mass (kg)
+    pub muscle_mass_kg: f64,
+    /// Bone Mass (kg)
+    pub bone_mass_kg: f64,
+}
+
+/// Calculates body composition metrics from the weight (kg) and the body impedance (Ohm) measured
+/// by a Mi Body Composition Scale using the Xiaomi algorithm.
+///
+/// ## References
+///
+/// - <https://github.com/oliexdev/openScale/blob/master/android_app/app/src/main/java/com/health/openscale/core/bluetooth/lib/MiScaleLib.java>
+#[must_use]
+pub fn body_metrics(weight_kg: f64, impedance: u16, profile: &Profile) -> BodyMetrics {
+    let age = f64::from(profile.age);
+    let height_cm = profile.height_cm;
+    let is_female = profile.sex == Sex::Female;
+
+    // Lean body mass coefficient
+    let lbm = (height_cm * 9.058 / 100.0) * (height_cm / 100.0) + weight_kg * 0.32 + 12.226
+        - f64::from(impedance) * 0.0068
+        - age * 0.0542;
+
+    let bmi = (weight_kg / ((height_cm / 100.0) * (height_cm / 100.0))).clamp(10.0, 90.0);
+
+    let body_fat_percent = {
+        let lbm_offset = match (profile.sex, profile.age) {
+            (Sex::Female, 0..=49) => 9.25,
+            (Sex::Female, _) => 7.25,
+            (Sex::Male, _) => 0.8,
+        };
+        let tall_factor = if height_cm > 160.0 { 1.03 } else { 1.0 };
+        let coefficient = match profile.sex {
+            Sex::Male if weight_kg < 61.0 => 0.98,
+            Sex::Female if weight_kg > 60.0 => 0.96 * tall_factor,
+            Sex::Female if weight_kg < 50.0 => 1.02 * tall_factor,
+            _ => 1.0,
+        };
+        let value = (1.0 - ((lbm - lbm_offset) * coefficient) / weight_kg) * 100.0;
+        if value > 63.0 {
+            75.0
+        } else {
+            value.clamp(5.0, 75.0)
+        }
+    };
+
+    let water_percent = {
+        let value = (100.0 - body_fat_percent) * 0.7;
+        let coefficient = if value <= 50.0 { 1.02 } else { 0.98 };
+        let value = if value * coefficient >= 65.0 {
+            75.0
+        } else {
+            value
+        };
+        (value * coefficient).clamp(35.0, 75.0)
+    };
+
+    let bone_mass_kg = {
+        let base = if is_female {
+            0.245_691_014
+        } else {
+            0.180_168_94
+        };
+        let value = lbm * 0.05158 - base;
+        let value = if value > 2.2 {
+            value + 0.1
+        } else {
+            value - 0.1
+        };
+        let limit = if is_female { 5.1 } else { 5.2 };
+        if value > limit {
+            8.0
+        } else {
+            value.clamp(0.5, 8.0)
+        }
+    };
+
+    let muscle_mass_kg = {
+        let value = weight_kg - body_fat_percent * 0.01 * weight_kg - bone_mass_kg;
+        let limit = if is_female { 84.0 } else { 93.5 };
+        if value >= limit {
+            120.0
+        } else {
+            value.clamp(10.0, 120.0)
+        }
+    };
+
+    BodyMetrics {
+        bmi,
+        body_fat_percent,
+        water_percent,
+        muscle_mass_kg,
+        bone_mass_kg,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        all_device_types, body_metrics, MiScalePacket, MiScaleServiceAdvertisement,
+        MiScaleTimestamp, PacketHeaderV1, PacketHeaderV2, Profile, Sex, WeightUnit,
+    };
+    use crate::sensor::{NumericMeasurementType, SensorEvent, UnitOfMeasurement};
+    use crate::ParseError;
+
+    const XMTZC01HM_POUNDS_READING: [u8; 14] = [
+        0x00, 0x00, 0x1D, 0x18, 0x21, 0x98, 0x3A, 0xE8, 0x07, 0x03, 0x18, 0x0A, 0x1B, 0x0C,
+    ];
+    const XMTZC02HM_STABILIZED_READING: [u8; 17] = [
+        0x00, 0x00, 0x1B, 0x18, 0x00, 0x44, 0xE8, 0x07, 0x03, 0x18, 0x0A, 0x1B, 0x0C, 0xF4, 0x01,
+        0xB0, 0x36,
+    ];
+    const XMTZC02HM_UNSTABILIZED_READING: [u8; 17] = [
+        0x00, 0x00, 0x1B, 0x18, 0x00, 0x00, 0xE8, 0x07, 0x03, 0x18, 0x0A, 0x1B, 0x0C, 0xF4, 0x01,
+        0xB0, 0x36,
+    ];
+    const XMTZC02HM_IMPEDANCE_UNSTABILIZED_READING: [u8; 17] = [
+        0x00, 0x00, 0x1B, 0x18, 0x00, 0x04, 0xE8, 0x07, 0x03, 0x18, 0x0A, 0x1B, 0x0C, 0xF4, 0x01,
+        0xB0, 0x36,
+    ];
+    const XMTZC01HM_CATTY_READING: [u8; 14] = [
+        0x00, 0x00, 0x1D, 0x18, 0x30, 0xC8, 0x32, 0xE8, 0x07, 0x03, 0x18, 0x0A, 0x1B, 0x0C,
+    ];
+
+    #[test]
+    fn enumerate_device_types() {
+        assert_eq!(all_device_types().count(), 2);
+        assert!(
+            all_device_types().any(|(device_id, device_type)| device_id == 0x181B
+                && device_type.name == "Mi Body Composition Scale")
+        );
+    }
+
+    #[test]
+    fn weight_in_pounds() {
+        let advertisement =
+            MiScaleServiceAdvertisement::from_slice(&XMTZC01HM_POUNDS_READING).unwrap();
+        assert_eq!(
+            advertisement.weight_with_unit(),
+            Some((150.0, UnitOfMeasurement::Pound))
+        );
+        assert!(matches!(
+            advertisement.iter_sensor_events().next(),
+            Some(SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Weight,
+                value,
+                unit: UnitOfMeasurement::Kilogram,
+            }) if (value - 68.038_855_5).abs() < 1e-6
+        ));
+    }
+
+    #[test]
+    fn weight_in_catty() {
+        let advertisement =
+            MiScaleServiceAdvertisement::from_slice(&XMTZC01HM_CATTY_READING).unwrap();
+        assert_eq!(
+            advertisement.weight_with_unit(),
+            Some((130.0, UnitOfMeasurement::Catty))
+        );
+        assert!(matches!(
+            advertisement.iter_sensor_events().next(),
+            Some(SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Weight,
+                value,
+                unit: UnitOfMeasurement::Kilogram,
+            }) if value == 65.0
+        ));
+    }
+
+    #[test]
+    fn header_weight_unit() {
+        let cases = [
+            (false, false, Some(WeightUnit::TwoHundredKilograms)),
+            (true, false, Some(WeightUnit::OneHundredPounds)),
+            (false, true, Some(WeightUnit::OneHundredCatty)),
+            (true, true, None),
+        ];
+        for (is_pounds, is_catty, unit) in cases {
+            let header = PacketHeaderV1::new()
+                .with_weight_unit_is_pounds(is_pounds)
+                .with_weight_unit_is_catty(is_catty);
+            assert_eq!(header.unit(), unit);
+            let header = PacketHeaderV2::new()
+                .with_weight_unit_is_pounds(is_pounds)
+                .with_weight_unit_is_catty(is_catty);
+            assert_eq!(header.unit(), unit);
+        }
+
+        assert!(PacketHeaderV1::new().weight_present());
+        assert!(!PacketHeaderV1::new()
+            .with_weight_removed(true)
+            .weight_present());
+        assert!(PacketHeaderV2::new().weight_present());
+        assert!(!PacketHeaderV2::new()
+            .with_weight_removed(true)
+            .weight_present());
+    }
+
+    #[test]
+    fn weight_unit_to_kilograms() {
+        assert_eq!(WeightUnit::TwoHundredKilograms.to_kilograms(14000), 70.0);
+        // 100 lb
+        assert!((WeightUnit::OneHundredPounds.to_kilograms(10000) - 45.359237).abs() < 1e-9);
+        assert_eq!(WeightUnit::OneHundredCatty.to_kilograms(14000), 70.0);
+        for unit in [
+            WeightUnit::TwoHundredKilograms,
+            WeightUnit::OneHundredPounds,
+            WeightUnit::OneHundredCatty,
+        ] {
+            assert_eq!(unit.to_kilograms(0), 0.0);
+        }
+    }
+
+    #[test]
+    fn weight_boundaries() {
+        let weight_with_unit = |header: u8, weight: u16| {
+            let mut reading = XMTZC01HM_POUNDS_READING;
+            reading[4] = header;
+            reading[5..7].copy_from_slice(&weight.to_le_bytes());
+            MiScaleServiceAdvertisement::from_slice(&reading)
+                .unwrap()
+                .weight_with_unit()
+        };
+
+        assert_eq!(
+            weight_with_unit(0x20, 30000),
+            Some((150.0, UnitOfMeasurement::Kilogram))
+        );
+        assert_eq!(weight_with_unit(0x20, 30001), None);
+        assert_eq!(weight_with_unit(0x20, u16::MAX), None);
+        assert_eq!(
+            weight_with_unit(0x21, 33069),
+            Some((330.69, UnitOfMeasurement::Pound))
+        );
+        assert_eq!(weight_with_unit(0x21, 33070), None);
+        assert_eq!(
+            weight_with_unit(0x30, 30000),
+            Some((300.0, UnitOfMeasurement::Catty))
+        );
+        assert_eq!(weight_with_unit(0x30, 30001), None);
+
+        // Both pounds and catty unit flags set.
+        assert_eq!(weight_with_unit(0x31, 10000), None);
+    }
+
+    #[test]
+    fn embedded_timestamp() {
+        let advertisement =
+            MiScaleServiceAdvertisement::from_slice(&XMTZC02HM_STABILIZED_READING).unwrap();
+        let MiScalePacket::MiScaleV2(packet) = &advertisement.payload else {
+            panic!("expected v2 packet");
+        };
+        assert_eq!(
+            packet.timestamp,
+            MiScaleTimestamp {
+                year: 2024,
+                month: 3,
+                day: 24,
+                hour: 10,
+                minute: 27,
+                second: 12,
+            }
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn embedded_timestamp_naive_datetime() {
+        use chrono::NaiveDate;
+
+        let advertisement =
+            MiScaleServiceAdvertisement::from_slice(&XMTZC02HM_STABILIZED_READING).unwrap();
+        let MiScalePacket::MiScaleV2(packet) = &advertisement.payload else {
+            panic!("expected v2 packet");
+        };
+        let expected = NaiveDate::from_ymd_opt(2024, 3, 24)
+            .and_then(|date| date.and_hms_opt(10, 27, 12))
+            .unwrap();
+        assert_eq!(packet.timestamp.to_naive_datetime(), Some(expected));
+        assert_eq!(advertisement.timestamp(), Some(packet.timestamp));
+    }
+
+    #[test]
+    fn readings_indicate_stability() {
+        let weight_is_final = |reading: &[u8]| {
+            let advertisement = MiScaleServiceAdvertisement::from_slice(reading).unwrap();
+            let readings = advertisement.iter_readings().collect::<Vec<_>>();
+            assert_eq!(
+                readings
+                    .iter()
+                    .map(|reading| reading.event)
+                    .collect::<Vec<_>>(),
+                advertisement.iter_sensor_events().collect::<Vec<_>>()
+            );
+            readings
+                .iter()
+                .find(|reading| {
+                    matches!(
+                        reading.event,
+                        SensorEvent::NumericMeasurement {
+                            measurement_type: NumericMeasurementType::Weight,
+                            ..
+                        }
+                    )
+                })
+                .unwrap()
+                .is_final
+        };
+
+        // v1
+        assert_eq!(weight_is_final(&XMTZC01HM_POUNDS_READING), Some(true));
+        let mut reading = XMTZC01HM_POUNDS_READING;
+        reading[4] = 0x01;
+        assert_eq!(weight_is_final(&reading), Some(false));
+
+        // v2
+        assert_eq!(weight_is_final(&XMTZC02HM_STABILIZED_READING), Some(true));
+        assert_eq!(
+            weight_is_final(&XMTZC02HM_UNSTABILIZED_READING),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn weight_stabilization() {
+        let advertisement =
+            MiScaleServiceAdvertisement::from_slice(&XMTZC02HM_STABILIZED_READING).unwrap();
+        assert!(advertisement.payload.weight_stabilized());
+        assert_eq!(
+            advertisement.weight_with_unit(),
+            Some((70.0, UnitOfMeasurement::Kilogram))
+        );
+        assert!(advertisement
+            .iter_stabilized_sensor_events()
+            .any(|event| matches!(
+                event,
+                SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Weight,
+                    ..
+                }
+            )));
+
+        let advertisement =
+            MiScaleServiceAdvertisement::from_slice(&XMTZC02HM_UNSTABILIZED_READING).unwrap();
+        assert!(!advertisement.payload.weight_stabilized());
+        assert!(advertisement.iter_sensor_events().any(|event| matches!(
+            event,
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Weight,
+                ..
+            }
+        )));
+        assert!(!advertisement
+            .iter_stabilized_sensor_events()
+            .any(|event| matches!(
+                event,
+                SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Weight,
+                    ..
+                }
+            )));
+    }
+
+    #[test]
+    fn impedance_stabilization() {
+        let is_impedance = |event: &SensorEvent| {
+            matches!(
+                event,
+                SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Impedance,
+                    ..
+                }
+            )
+        };
+
+        let advertisement =
+            MiScaleServiceAdvertisement::from_slice(&XMTZC02HM_STABILIZED_READING).unwrap();
+        assert!(advertisement.payload.impedance_stabilized());
+        assert!(advertisement
+            .iter_stabilized_sensor_events()
+            .any(|event| is_impedance(&event)));
+
+        let advertisement =
+            MiScaleServiceAdvertisement::from_slice(&XMTZC02HM_IMPEDANCE_UNSTABILIZED_READING)
+                .unwrap();
+        assert!(advertisement.payload.weight_stabilized());
+        assert!(!advertisement.payload.impedance_stabilized());
+        assert!(advertisement
+            .iter_sensor_events()
+            .any(|event| is_impedance(&event)));
+        let events: Vec<SensorEvent> = advertisement.iter_stabilized_sensor_events().collect();
+        assert_eq!(events.len(), 1);
+        assert!(!is_impedance(&events[0]));
+    }
+
+    #[test]
+    fn body_composition_metrics() {
+        let assert_close = |actual: f64, expected: f64| {
+            assert!(
+                (actual - expected).abs() < 1e-6,
+                "{actual} is not close to {expected}"
+            );
+        };
+
+        let profile = Profile {
+            age: 30,
+            height_cm: 180.0,
+            sex: Sex::Male,
+        };
+        let metrics = body_metrics(70.0, 500, &profile);
+        assert_close(metrics.bmi, 21.604_938_271_6);
+        assert_close(metrics.body_fat_percent, 16.931_542_857_1);
+        assert_close(metrics.water_percent, 56.984_961_6);
+        assert_close(metrics.muscle_mass_kg, 55.187_555_226_4);
+        assert_close(metrics.bone_mass_kg, 2.960_364_773_6);
+
+        let profile = Profile {
+            age: 52,
+            height_cm: 165.0,
+            sex: Sex::Female,
+        };
+        let metrics = body_metrics(55.0, 560, &profile);
+        assert_close(metrics.bmi, 20.202_020_202_0);
+        assert_close(metrics.body_fat_percent, 26.163_627_272_7);
+        assert_close(metrics.water_percent, 50.651_751_690_9);
+        assert_close(metrics.muscle_mass_kg, 38.287_076_956_1);
+        assert_close(metrics.bone_mass_kg, 2.322_928_043_9);
+    }
+
+    #[test]
+    fn invalid_device_id() {
+        let mut reading = XMTZC01HM_POUNDS_READING;
+        reading[2..4].copy_from_slice(&0x181Au16.to_le_bytes());
+        assert!(matches!(
+            MiScaleServiceAdvertisement::from_slice(&reading),
+            Err(ParseError::UnknownDeviceId { device_id: 0x181A })
+        ));
+    }
+
+    #[test]
+    fn truncated_packet() {
+        assert!(matches!(
+            MiScaleServiceAdvertisement::from_slice(&XMTZC01HM_POUNDS_READING[..10]),
+            Err(ParseError::Truncated {
+                expected: Some(14),
+                found: 10
+            })
+        ));
+        assert!(matches!(
+            MiScaleServiceAdvertisement::from_slice(&XMTZC02HM_STABILIZED_READING[..15]),
+            Err(ParseError::Truncated {
+                expected: Some(17),
+                found: 15
+            })
+        ));
+        assert!(matches!(
+            MiScaleServiceAdvertisement::from_slice(&XMTZC02HM_STABILIZED_READING[..3]),
+            Err(ParseError::Truncated {
+                expected: Some(4),
+                found: 3
+            })
+        ));
     }
 }
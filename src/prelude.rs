@@ -0,0 +1,61 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Commonly used types and functions.
+//!
+//! Import everything that is needed for typical usage (parsing an advertisement and handling its
+//! sensor events) with `use xiaomi_ble::prelude::*;`.
+
+pub use crate::device::{DeviceCategory, DeviceType};
+pub use crate::mibeacon::MacAddress;
+pub use crate::sensor::{
+    BinaryMeasurementType, DiscreteEvent, NumericMeasurementType, Reading, SensorEvent,
+    UnitOfMeasurement,
+};
+pub use crate::service::{ServiceAdvertisement, ServiceAdvertisementError};
+pub use crate::{
+    parse_manufacturer_data, parse_service_advertisement, parse_service_advertisement_u16,
+    try_parse_any, ParseError,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prelude_covers_typical_usage() {
+        const HHCCJCY01_TEMPERATURE_READING: [u8; 17] = [
+            0x71, 0x20, 0x98, 0x00, 0xB1, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x04, 0x10,
+            0x02, 0xEC, 0x00,
+        ];
+
+        let advertisement: ServiceAdvertisement =
+            parse_service_advertisement_u16(0xFE95, &HHCCJCY01_TEMPERATURE_READING).unwrap();
+        let device_type: &DeviceType = advertisement.device_type().unwrap();
+        assert_eq!(device_type.category(), DeviceCategory::PlantSensor);
+        assert_eq!(
+            advertisement.mac_address().map(MacAddress::from),
+            Some(MacAddress::from([0x11, 0x22, 0x33, 0x44, 0x55, 0x66]))
+        );
+        let events: Vec<SensorEvent> = advertisement.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            [SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Temperature,
+                value: 23.6,
+                unit: UnitOfMeasurement::DegreesCelsius,
+            }]
+        );
+        assert!(matches!(
+            parse_service_advertisement_u16(0xFE95, &[]),
+            Err(ServiceAdvertisementError::ParsingFailed(
+                ParseError::Truncated { .. }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright (c) 2024 Jan Holthuis <jan.holthuis@rub.de>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy
+// of the MPL was not distributed with this file, You can obtain one at
+// http://mozilla.org/MPL/2.0/.
+//
+// SPDX-License-Identifier: MPL-2.0
+
+//! Implementation of the PVVX custom firmware data structures.
+//!
+//! ## References
+//!
+//! - <https://github.com/pvvx/ATC_MiThermometer#custom-format-all-data-little-endian>
+//! - <https://github.com/Ernst79/bleparser/blob/c42ae922e1abed2720c7fac993777e1bd59c0c93/package/bleparser/atc.py>
+
+// FIXME: This lint is incompatible with `modular-bitfield` crate.
+#![allow(clippy::must_use_candidate)]
+
+use crate::device::DeviceType;
+use crate::mibeacon::MacAddress;
+use crate::sensor::{
+    BinaryMeasurementType, NumericMeasurementType, SensorEvent, UnitOfMeasurement,
+};
+use crate::ParseError;
+use alloc::borrow::Cow;
+use alloc::vec;
+use binrw::io::Cursor;
+use binrw::BinRead;
+use modular_bitfield::prelude::*;
+
+pub(crate) const PVVX_DEVICE: DeviceType = DeviceType {
+    name: Cow::Borrowed("Temperature/Humidity Sensor"),
+    model: Cow::Borrowed("PVVX"),
+    manufacturer: Cow::Borrowed("Xiaomi"),
+};
+
+/// Flags of a PVVX service advertisement.
+#[bitfield]
+#[derive(BinRead, Clone, Copy, PartialEq, Eq, Debug)]
+#[br(map = Self::from_bytes)]
+pub struct PvvxFlags {
+    /// Reed switch input state
+    pub reed_switch: bool,
+    /// GPIO trigger output state
+    pub gpio_trigger: bool,
+    /// Output control (0: controlled by trigger, 1: manually set)
+    pub output_control: bool,
+    /// Temperature trigger event
+    pub temperature_trigger: bool,
+    /// Humidity trigger event
+    pub humidity_trigger: bool,
+    /// Reserved
+    #[allow(dead_code)]
+    reserved: B3,
+}
+
+/// Service Advertisement in the PVVX custom firmware ("custom") format.
+#[derive(BinRead, Clone, PartialEq, Eq, Debug)]
+#[br(little)]
+pub struct PvvxServiceAdvertisement {
+    /// MAC Address
+    ///
+    /// The address is transmitted in reverse order, but stored in display order.
+    #[br(map = MacAddress::reversed)]
+    pub mac_address: MacAddress,
+    /// Temperature (0.01 °C)
+    pub temperature_centicelsius: i16,
+    /// Humidity (0.01 %)
+    pub humidity_centipercent: u16,
+    /// Battery Voltage (mV)
+    pub battery_millivolts: u16,
+    /// Battery Power (%)
+    pub battery_percent: u8,
+    /// Measurement Counter
+    pub counter: u8,
+    /// Flags
+    pub flags: PvvxFlags,
+}
+
+impl PvvxServiceAdvertisement {
+    /// Length of the service advertisement payload in bytes.
+    pub const LENGTH: usize = 15;
+
+    /// Parses a [PvvxServiceAdvertisement] from a byte slice.
+    pub fn from_slice(slice: &[u8]) -> Result<Self, ParseError> {
+        Self::read(&mut Cursor::new(slice))
+            .map_err(|err| ParseError::from_binrw(err, slice.len(), Some(Self::LENGTH)))
+    }
+
+    /// Get device type of advertisement sender.
+    pub const fn device_type(&self) -> &'static DeviceType {
+        &PVVX_DEVICE
+    }
+
+    /// Yields a list of sensor events parsed from the objects contained in the service advertisement.
+    pub fn iter_sensor_events(&self) -> impl Iterator<Item = SensorEvent> + '_ {
+        vec![
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Temperature,
+                value: f64::from(self.temperature_centicelsius) / 100.0,
+                unit: UnitOfMeasurement::DegreesCelsius,
+            },
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Humidity,
+                value: f64::from(self.humidity_centipercent) / 100.0,
+                unit: UnitOfMeasurement::Percent,
+            },
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::BatteryPower,
+                value: f64::from(self.battery_percent),
+                unit: UnitOfMeasurement::Percent,
+            },
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Voltage,
+                value: f64::from(self.battery_millivolts) / 1000.0,
+                unit: UnitOfMeasurement::Volt,
+            },
+            SensorEvent::BinaryMeasurement {
+                measurement_type: BinaryMeasurementType::ReedSwitch,
+                value: self.flags.reed_switch(),
+            },
+            SensorEvent::BinaryMeasurement {
+                measurement_type: BinaryMeasurementType::Trigger,
+                value: self.flags.gpio_trigger(),
+            },
+        ]
+        .into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PvvxServiceAdvertisement;
+    use crate::sensor::{
+        BinaryMeasurementType, NumericMeasurementType, SensorEvent, UnitOfMeasurement,
+    };
+
+    const LYWSD03MMC_PVVX_READING: [u8; 15] = [
+        0xA1, 0x6F, 0x2C, 0x38, 0xC1, 0xA4, 0x7D, 0x08, 0xC9, 0x14, 0xA3, 0x0B, 0x5D, 0x30, 0x05,
+    ];
+
+    #[test]
+    fn parse_lywsd03mmc_pvvx_reading() {
+        let message = PvvxServiceAdvertisement::from_slice(&LYWSD03MMC_PVVX_READING).unwrap();
+        assert_eq!(message.mac_address.to_string(), "A4:C1:38:2C:6F:A1");
+        assert_eq!(message.temperature_centicelsius, 2173);
+        assert_eq!(message.humidity_centipercent, 5321);
+        assert_eq!(message.battery_millivolts, 2979);
+        assert_eq!(message.battery_percent, 93);
+        assert_eq!(message.counter, 0x30);
+        assert!(message.flags.reed_switch());
+        assert!(!message.flags.gpio_trigger());
+        assert!(message.flags.output_control());
+
+        let events: Vec<SensorEvent> = message.iter_sensor_events().collect();
+        assert_eq!(events.len(), 6);
+        assert!(matches!(
+            events[0],
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Temperature,
+                value,
+                ..
+            } if value == 21.73
+        ));
+        assert!(matches!(
+            events[4],
+            SensorEvent::BinaryMeasurement {
+                measurement_type: BinaryMeasurementType::ReedSwitch,
+                value: true,
+            }
+        ));
+    }
+
+    #[test]
+    fn battery_voltage_in_volts() {
+        let mut reading = LYWSD03MMC_PVVX_READING;
+        reading[10..12].copy_from_slice(&3000u16.to_le_bytes());
+        let message = PvvxServiceAdvertisement::from_slice(&reading).unwrap();
+        assert_eq!(message.battery_millivolts, 3000);
+        assert!(message.iter_sensor_events().any(|event| matches!(
+            event,
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Voltage,
+                value,
+                unit: UnitOfMeasurement::Volt,
+            } if value == 3.0
+        )));
+    }
+}
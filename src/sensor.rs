@@ -9,10 +9,20 @@
 //! The sensor abstraction are used to access data regardless of the underlying protocol that is
 //! used.
 
+use crate::mibeacon::{
+    ArmingEventStatus, ButtonEventType, DimmerAction, DoorbellCameraEvent, FingerprintEventKeyId,
+    FingerprintEventMatchingResult, KettleState, LockAction, LockKeyCategory, LockMethod,
+    QuingpingPomodoroEvent, QuingpingSensorLocationEvent, RemoteButton, ToothbrushEventType,
+};
 use core::fmt;
 
 /// Represents a measured sensor value.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "kind", rename_all = "snake_case")
+)]
 pub enum SensorEvent {
     /// A binary measurement (true/false).
     BinaryMeasurement {
@@ -30,6 +40,59 @@ pub enum SensorEvent {
         /// The unit of the value.
         unit: UnitOfMeasurement,
     },
+    /// A discrete event (e.g. a lock operation).
+    DiscreteEvent(DiscreteEvent),
+}
+
+impl SensorEvent {
+    /// Get the kind of the measurement, or [`None`] for discrete events.
+    #[must_use]
+    pub fn measurement_kind(&self) -> Option<MeasurementKind> {
+        match &self {
+            Self::BinaryMeasurement {
+                measurement_type, ..
+            } => Some(MeasurementKind::Binary(*measurement_type)),
+            Self::NumericMeasurement {
+                measurement_type, ..
+            } => Some(MeasurementKind::Numeric(*measurement_type)),
+            Self::DiscreteEvent(_) => None,
+        }
+    }
+
+    /// Converts a numeric measurement to another unit (e.g. °C to °F or kg to lb).
+    ///
+    /// Returns [`None`] if the event is not a numeric measurement or if the units are
+    /// incompatible.
+    #[must_use]
+    pub fn converted_to(&self, unit: UnitOfMeasurement) -> Option<SensorEvent> {
+        let Self::NumericMeasurement {
+            measurement_type,
+            value,
+            unit: source_unit,
+        } = *self
+        else {
+            return None;
+        };
+
+        let value = match (source_unit, unit) {
+            (source_unit, unit) if source_unit == unit => value,
+            (UnitOfMeasurement::DegreesCelsius, UnitOfMeasurement::DegreesFahrenheit) => {
+                value * 9.0 / 5.0 + 32.0
+            }
+            (UnitOfMeasurement::DegreesFahrenheit, UnitOfMeasurement::DegreesCelsius) => {
+                (value - 32.0) * 5.0 / 9.0
+            }
+            (source_unit, unit) => {
+                value * source_unit.kilograms_per_unit()? / unit.kilograms_per_unit()?
+            }
+        };
+
+        Some(Self::NumericMeasurement {
+            measurement_type,
+            value,
+            unit,
+        })
+    }
 }
 
 impl fmt::Display for SensorEvent {
@@ -39,17 +102,280 @@ impl fmt::Display for SensorEvent {
                 measurement_type,
                 value,
             } => write!(f, "{} = {}", measurement_type, value),
+            Self::NumericMeasurement {
+                measurement_type,
+                value,
+                unit,
+            } if *unit == UnitOfMeasurement::Unitless => {
+                write!(f, "{} = {}", measurement_type, value)
+            }
             Self::NumericMeasurement {
                 measurement_type,
                 value,
                 unit,
             } => write!(f, "{} = {} {}", measurement_type, value, unit),
+            Self::DiscreteEvent(event) => write!(f, "{}", event),
+        }
+    }
+}
+
+/// A [`SensorEvent`] (or [`Reading`]) together with the time it was received.
+#[cfg(feature = "std")]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimestampedEvent<T = SensorEvent> {
+    /// The event.
+    pub event: T,
+    /// The time at which the advertisement containing the event was received.
+    pub received_at: std::time::SystemTime,
+}
+
+/// The kind of a measurement (without a value), e.g. to describe which measurements a device
+/// reports (see [`DeviceType::expected_measurements`](crate::device::DeviceType::expected_measurements)).
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum MeasurementKind {
+    /// A binary measurement (see [`SensorEvent::BinaryMeasurement`]).
+    Binary(BinaryMeasurementType),
+    /// A numeric measurement (see [`SensorEvent::NumericMeasurement`]).
+    Numeric(NumericMeasurementType),
+}
+
+/// A [`SensorEvent`] together with information about its origin.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reading {
+    /// The sensor event.
+    pub event: SensorEvent,
+    /// The ID of the MiBeacon object that the event was parsed from ([`None`] for other protocols).
+    pub source_object_id: Option<u16>,
+    /// Whether the measurement is final (i.e. stabilized) or still in progress, if the device
+    /// reports this (currently only Mi Scales, [`None`] otherwise).
+    pub is_final: Option<bool>,
+    /// The resolution of a numeric measurement as sent by the device (e.g. `0.01` if the device
+    /// reports hundredths), if known (currently only MiBeacon readings, [`None`] otherwise).
+    pub resolution: Option<f64>,
+}
+
+impl From<SensorEvent> for Reading {
+    fn from(event: SensorEvent) -> Self {
+        Self {
+            event,
+            source_object_id: None,
+            is_final: None,
+            resolution: None,
+        }
+    }
+}
+
+/// Represents a discrete event that happened at a certain point in time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "event", rename_all = "snake_case")
+)]
+pub enum DiscreteEvent {
+    /// A lock was operated.
+    Lock {
+        /// The performed action.
+        action: LockAction,
+        /// The method used for the operation.
+        method: LockMethod,
+        /// The key that was used for the operation.
+        key: LockKeyCategory,
+        /// Timestamp (UTC).
+        timestamp: u32,
+    },
+    /// A fingerprint was scanned.
+    Fingerprint {
+        /// The key that was matched.
+        key_id: FingerprintEventKeyId,
+        /// The result of the fingerprint matching.
+        matching_result: FingerprintEventMatchingResult,
+    },
+    /// A device requested to connect.
+    Connect {
+        /// ID of the object to be paired (e.g. `0x1001` for key events).
+        object_id: u16,
+    },
+    /// A device requested simple pairing.
+    SimplePairing {
+        /// ID of the object to be paired (e.g. `0x1001` for key events).
+        object_id: u16,
+    },
+    /// A device came close.
+    Proximity {
+        /// ID of the referenced object.
+        object_id: u16,
+    },
+    /// A device moved away.
+    KeepAway {
+        /// ID of the referenced object.
+        object_id: u16,
+    },
+    /// A lock was operated (deprecated format, superseded by [`DiscreteEvent::Lock`]).
+    LockDeprecated {
+        /// ID of the referenced object.
+        object_id: u16,
+    },
+    /// A security device was armed or disarmed.
+    Arming {
+        /// Whether the device was armed or disarmed.
+        status: ArmingEventStatus,
+        /// Timestamp (UTC), if reported by the device.
+        timestamp: Option<u32>,
+    },
+    /// A doorbell camera detected something.
+    Doorbell {
+        /// What was detected.
+        event_type: DoorbellCameraEvent,
+    },
+    /// A sensor was separated from or connected to its base.
+    SensorLocation {
+        /// The new location of the sensor.
+        location: QuingpingSensorLocationEvent,
+    },
+    /// A pomodoro timer changed its phase.
+    Pomodoro {
+        /// The phase that was started or ended.
+        phase: QuingpingPomodoroEvent,
+    },
+    /// Tooth brushing was started or ended.
+    Toothbrush {
+        /// Whether brushing started or ended.
+        event_type: ToothbrushEventType,
+        /// Timestamp (UTC), if reported by the device.
+        timestamp: Option<u32>,
+    },
+    /// The knob of a dimmer was pressed or rotated.
+    Dimmer {
+        /// The performed action (including the rotation direction).
+        action: DimmerAction,
+        /// Number of rotation steps or presses, or press duration in seconds (see
+        /// [`DimmerAction`]).
+        value: u8,
+    },
+    /// The state of a kettle was reported.
+    Kettle {
+        /// The kettle state (derived from the power state and the water temperature).
+        state: KettleState,
+    },
+    /// A button of a remote control was pressed.
+    RemoteButton {
+        /// The pressed button (the labels depend on the remote control model).
+        button: RemoteButton,
+        /// Single click, double click or long press.
+        event_type: ButtonEventType,
+    },
+}
+
+impl DiscreteEvent {
+    /// Get the lowercase name of this event.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match &self {
+            Self::Lock { .. } => "lock",
+            Self::Fingerprint { .. } => "fingerprint",
+            Self::Connect { .. } => "connect",
+            Self::SimplePairing { .. } => "simple_pairing",
+            Self::Proximity { .. } => "proximity",
+            Self::KeepAway { .. } => "keep_away",
+            Self::LockDeprecated { .. } => "lock_deprecated",
+            Self::Arming { .. } => "arming",
+            Self::Doorbell { .. } => "doorbell",
+            Self::SensorLocation { .. } => "sensor_location",
+            Self::Pomodoro { .. } => "pomodoro",
+            Self::Toothbrush { .. } => "toothbrush",
+            Self::Dimmer { .. } => "dimmer",
+            Self::Kettle { .. } => "kettle",
+            Self::RemoteButton { .. } => "remote_button",
+        }
+    }
+
+    /// Get the timestamp of the event (if any) as [`chrono::DateTime`].
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn timestamp_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        match &self {
+            Self::Lock { timestamp, .. } => Some(*timestamp),
+            Self::Arming { timestamp, .. } | Self::Toothbrush { timestamp, .. } => *timestamp,
+            Self::Fingerprint { .. }
+            | Self::Connect { .. }
+            | Self::SimplePairing { .. }
+            | Self::Proximity { .. }
+            | Self::KeepAway { .. }
+            | Self::LockDeprecated { .. }
+            | Self::Doorbell { .. }
+            | Self::SensorLocation { .. }
+            | Self::Pomodoro { .. }
+            | Self::Dimmer { .. }
+            | Self::Kettle { .. }
+            | Self::RemoteButton { .. } => None,
+        }
+        .map(crate::mibeacon::timestamp_utc)
+    }
+}
+
+impl fmt::Display for DiscreteEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::Lock {
+                action,
+                method,
+                key,
+                ..
+            } => write!(f, "{} = {} ({}, {})", self.as_str(), action, method, key),
+            Self::Fingerprint {
+                key_id: FingerprintEventKeyId::KeyId(key_id),
+                matching_result,
+            } => write!(
+                f,
+                "{} = {} (key_id {})",
+                self.as_str(),
+                matching_result,
+                key_id
+            ),
+            Self::Fingerprint {
+                key_id,
+                matching_result,
+            } => write!(f, "{} = {} ({})", self.as_str(), matching_result, key_id),
+            Self::Connect { object_id }
+            | Self::SimplePairing { object_id }
+            | Self::Proximity { object_id }
+            | Self::KeepAway { object_id }
+            | Self::LockDeprecated { object_id } => {
+                write!(f, "{} = 0x{:04X}", self.as_str(), object_id)
+            }
+            Self::Arming { status, .. } => write!(f, "{} = {}", self.as_str(), status),
+            Self::Doorbell { event_type } => write!(f, "{} = {}", self.as_str(), event_type),
+            Self::SensorLocation { location } => write!(f, "{} = {}", self.as_str(), location),
+            Self::Pomodoro { phase } => write!(f, "{} = {}", self.as_str(), phase),
+            Self::Toothbrush { event_type, .. } => {
+                write!(f, "{} = {}", self.as_str(), event_type)
+            }
+            Self::Dimmer { action, value } => {
+                write!(f, "{} = {} ({})", self.as_str(), action, value)
+            }
+            Self::Kettle { state } => write!(f, "{} = {}", self.as_str(), state),
+            Self::RemoteButton { button, event_type } => {
+                write!(f, "{} = {} ({})", self.as_str(), button, event_type)
+            }
         }
     }
 }
 
 /// Measurement type for binary sensors.
 #[derive(Copy, PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum BinaryMeasurementType {
     /// Power State.
     Power,
@@ -65,6 +391,18 @@ pub enum BinaryMeasurementType {
     GasLeak,
     /// Light State.
     Light,
+    /// Lock State.
+    Lock,
+    /// Anti-Lock State.
+    AntiLock,
+    /// Child Lock State.
+    ChildLock,
+    /// Reed Switch State.
+    ReedSwitch,
+    /// Trigger State.
+    Trigger,
+    /// Plugged-In State (e.g. of a sensor probe).
+    PluggedIn,
 }
 
 impl BinaryMeasurementType {
@@ -79,8 +417,48 @@ impl BinaryMeasurementType {
             Self::WaterImmersion => "water_immersion",
             Self::GasLeak => "gas_leak",
             Self::Light => "light",
+            Self::Lock => "lock",
+            Self::AntiLock => "anti_lock",
+            Self::ChildLock => "child_lock",
+            Self::ReedSwitch => "reed_switch",
+            Self::Trigger => "trigger",
+            Self::PluggedIn => "plugged_in",
+        }
+    }
+
+    /// Get the corresponding [Home Assistant binary sensor device class][ha], if any.
+    ///
+    /// Types where the meaning of `true` differs from the Home Assistant device class (e.g. for
+    /// [`Self::Lock`], `true` means locked, but Home Assistant's `lock` device class uses `on` for
+    /// unlocked) are not mapped.
+    ///
+    /// [ha]: https://www.home-assistant.io/integrations/binary_sensor/#device-class
+    #[must_use]
+    pub fn device_class(&self) -> Option<&'static str> {
+        match &self {
+            Self::Power => Some("power"),
+            Self::WaterImmersion => Some("moisture"),
+            Self::GasLeak => Some("gas"),
+            Self::Light => Some("light"),
+            Self::PluggedIn => Some("plug"),
+            Self::Sleep
+            | Self::Binding
+            | Self::Switch
+            | Self::Lock
+            | Self::AntiLock
+            | Self::ChildLock
+            | Self::ReedSwitch
+            | Self::Trigger => None,
         }
     }
+
+    /// Get the corresponding Home Assistant state class, if any.
+    ///
+    /// Home Assistant binary sensors do not have a state class, so this always returns [`None`].
+    #[must_use]
+    pub fn state_class(&self) -> Option<&'static str> {
+        None
+    }
 }
 
 impl fmt::Display for BinaryMeasurementType {
@@ -91,6 +469,11 @@ impl fmt::Display for BinaryMeasurementType {
 
 /// Measurement type for numeric sensors.
 #[derive(Copy, PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum NumericMeasurementType {
     /// A temperature measurement.
     Temperature,
@@ -109,9 +492,28 @@ pub enum NumericMeasurementType {
     /// A battery power measurement.
     BatteryPower,
     /// A weight measurement.
+    ///
+    /// Weights are always reported in [`UnitOfMeasurement::Kilogram`], even if the device uses a
+    /// different unit (use [`SensorEvent::converted_to`] to get the value in another unit).
     Weight,
     /// An impedance measurement.
     Impedance,
+    /// A voltage measurement.
+    Voltage,
+    /// A Total Volatile Organic Compounds (TVOC) concentration measurement.
+    Tvoc,
+    /// A PM2.5 (fine particulate matter) concentration measurement.
+    Pm25,
+    /// A PM10 (coarse particulate matter) concentration measurement.
+    Pm10,
+    /// A Carbon Dioxide (CO₂) concentration measurement.
+    Co2,
+    /// An atmospheric pressure measurement.
+    Pressure,
+    /// A tooth brushing score (0 - 100).
+    ///
+    /// The score is reported as [`UnitOfMeasurement::Unitless`] value.
+    ToothbrushScore,
 }
 
 impl NumericMeasurementType {
@@ -129,8 +531,51 @@ impl NumericMeasurementType {
             Self::BatteryPower => "battery_power",
             Self::Weight => "weight",
             Self::Impedance => "impedance",
+            Self::Voltage => "voltage",
+            Self::Tvoc => "tvoc",
+            Self::Pm25 => "pm25",
+            Self::Pm10 => "pm10",
+            Self::Co2 => "co2",
+            Self::Pressure => "pressure",
+            Self::ToothbrushScore => "toothbrush_score",
         }
     }
+
+    /// Get the corresponding [Home Assistant sensor device class][ha], if any.
+    ///
+    /// [ha]: https://www.home-assistant.io/integrations/sensor/#device-class
+    #[must_use]
+    pub fn device_class(&self) -> Option<&'static str> {
+        match &self {
+            Self::Temperature => Some("temperature"),
+            Self::Humidity => Some("humidity"),
+            Self::Illuminance => Some("illuminance"),
+            Self::Moisture => Some("moisture"),
+            Self::Conductivity => Some("conductivity"),
+            Self::BatteryPower => Some("battery"),
+            Self::Weight => Some("weight"),
+            Self::Voltage => Some("voltage"),
+            Self::Tvoc => Some("volatile_organic_compounds"),
+            Self::Pm25 => Some("pm25"),
+            Self::Pm10 => Some("pm10"),
+            Self::Co2 => Some("carbon_dioxide"),
+            Self::Pressure => Some("atmospheric_pressure"),
+            Self::FormaldehydeConcentration
+            | Self::RemainingSupplies
+            | Self::Impedance
+            | Self::ToothbrushScore => None,
+        }
+    }
+
+    /// Get the corresponding [Home Assistant sensor state class][ha], if any.
+    ///
+    /// All numeric measurements are momentary values, so this is `measurement` for all types.
+    ///
+    /// [ha]: https://developers.home-assistant.io/docs/core/entity/sensor/#available-state-classes
+    #[must_use]
+    pub fn state_class(&self) -> Option<&'static str> {
+        Some("measurement")
+    }
 }
 
 impl fmt::Display for NumericMeasurementType {
@@ -141,23 +586,59 @@ impl fmt::Display for NumericMeasurementType {
 
 /// The unit of measurement.
 #[derive(Copy, PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnitOfMeasurement {
     /// Degrees Celsius (°C)
+    #[cfg_attr(feature = "serde", serde(rename = "°C"))]
     DegreesCelsius,
+    /// Degrees Fahrenheit (°F)
+    #[cfg_attr(feature = "serde", serde(rename = "°F"))]
+    DegreesFahrenheit,
     /// Percent (%)
+    #[cfg_attr(feature = "serde", serde(rename = "%"))]
     Percent,
     /// Lux (lx)
+    #[cfg_attr(feature = "serde", serde(rename = "lx"))]
     Lux,
     /// Microsiemens per Centimeter (µS/cm)
+    #[cfg_attr(feature = "serde", serde(rename = "µS/cm"))]
     MicrosiemensPerCentimeter,
     /// Milligram per Cubic Meter (mg/m³)
+    #[cfg_attr(feature = "serde", serde(rename = "mg/m³"))]
     MilligramPerCubicMeter,
+    /// Microgram per Cubic Meter (µg/m³)
+    #[cfg_attr(feature = "serde", serde(rename = "µg/m³"))]
+    MicrogramPerCubicMeter,
+    /// Parts per Million (ppm)
+    #[cfg_attr(feature = "serde", serde(rename = "ppm"))]
+    PartsPerMillion,
+    /// Hectopascal (hPa)
+    #[cfg_attr(feature = "serde", serde(rename = "hPa"))]
+    Hectopascal,
     /// Seconds (s)
+    #[cfg_attr(feature = "serde", serde(rename = "s"))]
     Seconds,
+    /// Gram (g)
+    #[cfg_attr(feature = "serde", serde(rename = "g"))]
+    Gram,
     /// Kilogram (kg)
+    #[cfg_attr(feature = "serde", serde(rename = "kg"))]
     Kilogram,
+    /// Pound (lb)
+    #[cfg_attr(feature = "serde", serde(rename = "lb"))]
+    Pound,
+    /// Chinese (Market) Catty (jin)
+    #[cfg_attr(feature = "serde", serde(rename = "jin"))]
+    Catty,
     /// Ohm (Ω)
+    #[cfg_attr(feature = "serde", serde(rename = "Ω"))]
     Ohm,
+    /// Volt (V)
+    #[cfg_attr(feature = "serde", serde(rename = "V"))]
+    Volt,
+    /// No unit (e.g. for scores)
+    #[cfg_attr(feature = "serde", serde(rename = ""))]
+    Unitless,
 }
 
 impl UnitOfMeasurement {
@@ -166,13 +647,33 @@ impl UnitOfMeasurement {
     pub fn as_str(&self) -> &'static str {
         match &self {
             Self::DegreesCelsius => "°C",
+            Self::DegreesFahrenheit => "°F",
             Self::Percent => "%",
             Self::Lux => "lx",
             Self::MicrosiemensPerCentimeter => "µS/cm",
             Self::MilligramPerCubicMeter => "mg/m³",
+            Self::MicrogramPerCubicMeter => "µg/m³",
+            Self::PartsPerMillion => "ppm",
+            Self::Hectopascal => "hPa",
             Self::Seconds => "s",
+            Self::Gram => "g",
             Self::Kilogram => "kg",
+            Self::Pound => "lb",
+            Self::Catty => "jin",
             Self::Ohm => "Ω",
+            Self::Volt => "V",
+            Self::Unitless => "",
+        }
+    }
+
+    /// Get the weight of one unit in kilograms (for weight units only).
+    fn kilograms_per_unit(self) -> Option<f64> {
+        match self {
+            Self::Gram => Some(0.001),
+            Self::Kilogram => Some(1.0),
+            Self::Pound => Some(0.453_592_37),
+            Self::Catty => Some(0.5),
+            _ => None,
         }
     }
 }
@@ -182,3 +683,137 @@ impl fmt::Display for UnitOfMeasurement {
         write!(f, "{}", self.as_str())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryMeasurementType, NumericMeasurementType, SensorEvent, UnitOfMeasurement};
+
+    #[test]
+    fn unit_conversion() {
+        let temperature = SensorEvent::NumericMeasurement {
+            measurement_type: NumericMeasurementType::Temperature,
+            value: 0.0,
+            unit: UnitOfMeasurement::DegreesCelsius,
+        };
+        assert!(matches!(
+            temperature.converted_to(UnitOfMeasurement::DegreesFahrenheit),
+            Some(SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Temperature,
+                value,
+                unit: UnitOfMeasurement::DegreesFahrenheit,
+            }) if value == 32.0
+        ));
+        assert!(temperature
+            .converted_to(UnitOfMeasurement::Kilogram)
+            .is_none());
+
+        let weight = SensorEvent::NumericMeasurement {
+            measurement_type: NumericMeasurementType::Weight,
+            value: 0.453_592_37,
+            unit: UnitOfMeasurement::Kilogram,
+        };
+        assert!(matches!(
+            weight.converted_to(UnitOfMeasurement::Pound),
+            Some(SensorEvent::NumericMeasurement {
+                value,
+                unit: UnitOfMeasurement::Pound,
+                ..
+            }) if (value - 1.0).abs() < 1e-9
+        ));
+        assert!(weight.converted_to(UnitOfMeasurement::Percent).is_none());
+
+        let weight = SensorEvent::NumericMeasurement {
+            measurement_type: NumericMeasurementType::Weight,
+            value: 3000.0,
+            unit: UnitOfMeasurement::Gram,
+        };
+        assert!(matches!(
+            weight.converted_to(UnitOfMeasurement::Kilogram),
+            Some(SensorEvent::NumericMeasurement {
+                value,
+                unit: UnitOfMeasurement::Kilogram,
+                ..
+            }) if (value - 3.0).abs() < 1e-9
+        ));
+    }
+
+    #[test]
+    fn home_assistant_classes() {
+        assert_eq!(
+            NumericMeasurementType::Temperature.device_class(),
+            Some("temperature")
+        );
+        assert_eq!(
+            NumericMeasurementType::BatteryPower.device_class(),
+            Some("battery")
+        );
+        assert_eq!(NumericMeasurementType::Impedance.device_class(), None);
+        assert_eq!(
+            NumericMeasurementType::Humidity.state_class(),
+            Some("measurement")
+        );
+        assert_eq!(
+            BinaryMeasurementType::WaterImmersion.device_class(),
+            Some("moisture")
+        );
+        assert_eq!(BinaryMeasurementType::Lock.device_class(), None);
+        assert_eq!(BinaryMeasurementType::Power.state_class(), None);
+    }
+
+    #[test]
+    fn air_quality_display() {
+        assert_eq!(NumericMeasurementType::Tvoc.to_string(), "tvoc");
+        assert_eq!(NumericMeasurementType::Pm25.to_string(), "pm25");
+        assert_eq!(NumericMeasurementType::Pm10.to_string(), "pm10");
+        assert_eq!(NumericMeasurementType::Co2.to_string(), "co2");
+        assert_eq!(
+            UnitOfMeasurement::MicrogramPerCubicMeter.to_string(),
+            "µg/m³"
+        );
+        assert_eq!(UnitOfMeasurement::PartsPerMillion.to_string(), "ppm");
+    }
+
+    #[test]
+    fn pressure_display() {
+        let event = SensorEvent::NumericMeasurement {
+            measurement_type: NumericMeasurementType::Pressure,
+            value: 1013.25,
+            unit: UnitOfMeasurement::Hectopascal,
+        };
+        assert_eq!(event.to_string(), "pressure = 1013.25 hPa");
+    }
+
+    #[test]
+    fn unitless_display() {
+        let event = SensorEvent::NumericMeasurement {
+            measurement_type: NumericMeasurementType::ToothbrushScore,
+            value: 90.0,
+            unit: UnitOfMeasurement::Unitless,
+        };
+        assert_eq!(event.to_string(), "toothbrush_score = 90");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let event = SensorEvent::NumericMeasurement {
+            measurement_type: NumericMeasurementType::Temperature,
+            value: 23.6,
+            unit: UnitOfMeasurement::DegreesCelsius,
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            json,
+            r#"{"kind":"numeric_measurement","measurement_type":"temperature","value":23.6,"unit":"°C"}"#
+        );
+        let parsed: SensorEvent = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            parsed,
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Temperature,
+                value,
+                unit: UnitOfMeasurement::DegreesCelsius,
+            } if value == 23.6
+        ));
+    }
+}
@@ -8,12 +8,19 @@
 
 //! Service-related functions.
 
-use crate::device::DeviceType;
+use crate::atc::AtcServiceAdvertisement;
+use crate::device::{DeviceRegistry, DeviceType};
 use crate::hhccjcy10::HHCCJCY10ServiceAdvertisement;
-use crate::mibeacon::MiBeaconServiceAdvertisement;
+use crate::mibeacon::{AuthMode, MacAddress, MiBeaconServiceAdvertisement, UnhandledObject};
 use crate::miscale::MiScaleServiceAdvertisement;
-use crate::sensor::SensorEvent;
+use crate::pvvx::PvvxServiceAdvertisement;
+#[cfg(feature = "std")]
+use crate::sensor::TimestampedEvent;
+use crate::sensor::{Reading, SensorEvent};
 use crate::util::ParseError;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
 
 use uuid::Uuid;
 
@@ -28,10 +35,13 @@ pub enum ServiceAdvertisementError {
     /// The UUID of the service advertisement is unknown/unhandled.
     #[error("Unhandled service advertisement UUID")]
     UnhandledService,
+    /// The company identifier of the manufacturer data is unknown/unhandled.
+    #[error("Unhandled manufacturer data company identifier")]
+    UnhandledManufacturer,
 }
 
 /// The BLE service advertisement type.
-#[derive(Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum ServiceType {
     /// MiBeacon
     MiBeacon,
@@ -41,10 +51,12 @@ pub enum ServiceType {
     Scale1,
     /// Mi Body Composition Scale
     Scale2,
+    /// ATC1441 or PVVX Custom Firmware (distinguished by payload length)
+    Atc,
 }
 
 /// A parsed BLE service advertisement.
-#[derive(Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub enum ServiceAdvertisement {
     /// A parsed MiBeacon service advertisement.
     MiBeacon(MiBeaconServiceAdvertisement),
@@ -52,6 +64,10 @@ pub enum ServiceAdvertisement {
     HHCCJCY10(HHCCJCY10ServiceAdvertisement),
     /// A parsed Mi Scale (v1/v2) service advertisement.
     MiScale(MiScaleServiceAdvertisement),
+    /// A parsed ATC1441 custom firmware service advertisement.
+    Atc(AtcServiceAdvertisement),
+    /// A parsed PVVX custom firmware service advertisement.
+    Pvvx(PvvxServiceAdvertisement),
 }
 
 impl ServiceAdvertisement {
@@ -62,6 +78,29 @@ impl ServiceAdvertisement {
             Self::MiBeacon(parsed_adverisement) => parsed_adverisement.device_type(),
             Self::HHCCJCY10(parsed_adverisement) => parsed_adverisement.device_type().into(),
             Self::MiScale(parsed_adverisement) => parsed_adverisement.device_type(),
+            Self::Atc(parsed_adverisement) => parsed_adverisement.device_type().into(),
+            Self::Pvvx(parsed_adverisement) => parsed_adverisement.device_type().into(),
+        }
+    }
+
+    /// Returns the advertisement together with the device type of its sender (see
+    /// [`Self::device_type`]), e.g. to get both right after parsing.
+    #[must_use]
+    pub fn into_parts(self) -> (Self, Option<&'static DeviceType>) {
+        let device_type = self.device_type();
+        (self, device_type)
+    }
+
+    /// Get device type of advertisement sender, taking custom device types from the `registry`
+    /// into account (see [`DeviceRegistry`]).
+    #[must_use]
+    pub fn device_type_with_registry<'a>(
+        &self,
+        registry: &'a DeviceRegistry,
+    ) -> Option<&'a DeviceType> {
+        match &self {
+            Self::MiBeacon(parsed_adverisement) => registry.lookup(parsed_adverisement.device_id),
+            _ => self.device_type(),
         }
     }
 
@@ -78,45 +117,277 @@ impl ServiceAdvertisement {
             Self::MiScale(parsed_adverisement) => {
                 Box::new(parsed_adverisement.iter_sensor_events())
             }
+            Self::Atc(parsed_adverisement) => Box::new(parsed_adverisement.iter_sensor_events()),
+            Self::Pvvx(parsed_adverisement) => Box::new(parsed_adverisement.iter_sensor_events()),
+        }
+    }
+
+    /// Collects the sensor events, together with the objects that did not yield any sensor events
+    /// (see [`MiBeaconServiceAdvertisement::try_iter_sensor_events`]).
+    ///
+    /// Only MiBeacon advertisements consist of separate objects, so for all other protocols the
+    /// list of unhandled objects is always empty.
+    #[must_use]
+    pub fn try_iter_sensor_events(&self) -> (Vec<SensorEvent>, Vec<UnhandledObject>) {
+        match &self {
+            Self::MiBeacon(parsed_adverisement) => parsed_adverisement.try_iter_sensor_events(),
+            _ => (self.iter_sensor_events().collect(), Vec::new()),
+        }
+    }
+
+    /// Yields the sensor events, each stamped with the time `received_at` at which the
+    /// advertisement was received.
+    #[cfg(feature = "std")]
+    pub fn iter_sensor_events_at(
+        &self,
+        received_at: std::time::SystemTime,
+    ) -> impl Iterator<Item = TimestampedEvent> + '_ {
+        self.iter_sensor_events()
+            .map(move |event| TimestampedEvent { event, received_at })
+    }
+
+    /// Collects the sensor events, keeping only the last event of each measurement kind (see
+    /// [`SensorEvent::measurement_kind`]).
+    ///
+    /// This is useful for devices that report the same measurement in several objects of the
+    /// same advertisement (e.g. both formaldehyde concentration objects). Each measurement
+    /// keeps the position of its first occurrence; discrete events are never deduplicated.
+    #[must_use]
+    pub fn latest_events(&self) -> Vec<SensorEvent> {
+        let mut events: Vec<SensorEvent> = Vec::new();
+        for event in self.iter_sensor_events() {
+            let kind = event.measurement_kind();
+            match events
+                .iter_mut()
+                .find(|previous| kind.is_some() && previous.measurement_kind() == kind)
+            {
+                Some(previous) => *previous = event,
+                None => events.push(event),
+            }
+        }
+        events
+    }
+
+    /// Yields the sensor events together with their origin (i.e. the source MiBeacon object ID).
+    ///
+    /// For protocols other than MiBeacon, the source object ID is always [`None`]. Mi Scale
+    /// readings also indicate whether the measurement is final (see [`Reading::is_final`]).
+    #[must_use]
+    pub fn iter_readings(&self) -> Box<dyn Iterator<Item = Reading> + Send + '_> {
+        match &self {
+            Self::MiBeacon(parsed_adverisement) => Box::new(parsed_adverisement.iter_readings()),
+            Self::MiScale(parsed_adverisement) => Box::new(parsed_adverisement.iter_readings()),
+            _ => Box::new(self.iter_sensor_events().map(Reading::from)),
+        }
+    }
+
+    /// Get the MAC address of the advertisement sender (if included in the advertisement).
+    ///
+    /// MiBeacon advertisements only include the MAC address if
+    /// [`FrameControl::mac_included()`](crate::mibeacon::FrameControl::mac_included) is set, and
+    /// HHCCJCY10 and Mi Scale advertisements never include it. In that case, the address of the
+    /// BLE peripheral has to be used instead.
+    ///
+    /// The address is always returned in display order (most significant byte first), even for
+    /// formats that transmit it in reverse order, so it can be compared to the peripheral address.
+    #[must_use]
+    pub fn mac_address(&self) -> Option<[u8; 6]> {
+        self.mac_address_ref()
+            .map(|mac_address| mac_address.into_inner())
+    }
+
+    /// Get the packet counter of the advertisement (if the format includes one), i.e. the
+    /// MiBeacon [`packet_id`](MiBeaconServiceAdvertisement::packet_id) or the frame counter of
+    /// the ATC/PVVX custom firmwares.
+    ///
+    /// Together with the MAC address, this can be used to skip repeated advertisements.
+    #[must_use]
+    pub fn sequence_number(&self) -> Option<u8> {
+        match &self {
+            Self::MiBeacon(parsed_adverisement) => Some(parsed_adverisement.packet_id),
+            Self::Atc(parsed_adverisement) => Some(parsed_adverisement.frame_counter),
+            Self::Pvvx(parsed_adverisement) => Some(parsed_adverisement.counter),
+            Self::HHCCJCY10(_) | Self::MiScale(_) => None,
+        }
+    }
+
+    /// Get a reference to the MAC address of the advertisement sender (if included in the
+    /// advertisement).
+    fn mac_address_ref(&self) -> Option<&MacAddress> {
+        match &self {
+            Self::MiBeacon(parsed_adverisement) => parsed_adverisement.mac_address.as_ref(),
+            Self::Atc(parsed_adverisement) => Some(&parsed_adverisement.mac_address),
+            Self::Pvvx(parsed_adverisement) => Some(&parsed_adverisement.mac_address),
+            Self::HHCCJCY10(_) | Self::MiScale(_) => None,
+        }
+    }
+
+    /// Get a flat summary of this advertisement (e.g. for serializing it to JSON).
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn summary(&self) -> ServiceAdvertisementSummary<'_> {
+        ServiceAdvertisementSummary {
+            device_type: self.device_type(),
+            mac_address: self.mac_address_ref(),
+            events: self.iter_sensor_events().collect(),
+        }
+    }
+}
+
+impl fmt::Display for ServiceAdvertisement {
+    /// Prints the device model and MAC address (if known) followed by one sensor event per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.device_type() {
+            Some(device_type) => write!(f, "{}", device_type.model)?,
+            None => write!(f, "Unknown device")?,
+        }
+        if let Some(mac_address) = self.mac_address_ref() {
+            write!(f, " ({})", mac_address)?;
+        }
+        for event in self.iter_sensor_events() {
+            write!(f, "\n{}", event)?;
         }
+        Ok(())
     }
 }
 
+/// A flat, serializable summary of a [`ServiceAdvertisement`].
+#[cfg(feature = "serde")]
+#[derive(Debug, serde::Serialize)]
+pub struct ServiceAdvertisementSummary<'a> {
+    /// Device type of the advertisement sender (if known).
+    pub device_type: Option<&'static DeviceType>,
+    /// MAC address of the advertisement sender (if included in the advertisement).
+    pub mac_address: Option<&'a MacAddress>,
+    /// Sensor events parsed from the advertisement.
+    pub events: Vec<SensorEvent>,
+}
+
+/// The Bluetooth Base UUID (`00000000-0000-1000-8000-00805F9B34FB`) that 16-bit service UUIDs
+/// are derived from.
+const BLUETOOTH_BASE_UUID: u128 = 0x0000_0000_0000_1000_8000_0080_5f9b_34fb;
+
 /// Maps a BLE service advertisement [UUID][Uuid] to a a [ServiceType].
+///
+/// Only UUIDs derived from the Bluetooth Base UUID (`0000xxxx-0000-1000-8000-00805F9B34FB`) are
+/// recognized, see [`service_type_from_u16`].
 #[must_use]
 pub const fn service_uuid_to_type(uuid: &Uuid) -> Option<ServiceType> {
-    const MIBEACON_UUID: Uuid = Uuid::from_bytes([
-        0x00, 0x00, 0xfe, 0x95, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0x80, 0x5f, 0x9b, 0x34,
-        0xfb,
-    ]);
-    const HHCCJCY10_UUID: Uuid = Uuid::from_bytes([
-        0x00, 0x00, 0xfd, 0x50, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0x80, 0x5f, 0x9b, 0x34,
-        0xfb,
-    ]);
-    const SCALE1_UUID: Uuid = Uuid::from_bytes([
-        0x00, 0x00, 0x18, 0x1d, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0x80, 0x5f, 0x9b, 0x34,
-        0xfb,
-    ]);
-    const SCALE2_UUID: Uuid = Uuid::from_bytes([
-        0x00, 0x00, 0x18, 0x1b, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0x80, 0x5f, 0x9b, 0x34,
-        0xfb,
-    ]);
-
-    match *uuid {
-        MIBEACON_UUID => Some(ServiceType::MiBeacon),
-        HHCCJCY10_UUID => Some(ServiceType::HHCCJCY10),
-        SCALE1_UUID => Some(ServiceType::Scale1),
-        SCALE2_UUID => Some(ServiceType::Scale2),
+    const SHORT_UUID_MASK: u128 = 0xFFFF << 96;
+
+    let value = uuid.as_u128();
+    if value & !SHORT_UUID_MASK != BLUETOOTH_BASE_UUID {
+        return None;
+    }
+    // The mask guarantees that the shifted value fits into 16 bits.
+    service_type_from_u16((value >> 96) as u16)
+}
+
+/// Maps a 16-bit BLE service UUID (as reported by many BLE stacks) to a [ServiceType].
+#[must_use]
+pub const fn service_type_from_u16(id: u16) -> Option<ServiceType> {
+    match id {
+        0xFE95 => Some(ServiceType::MiBeacon),
+        0xFD50 => Some(ServiceType::HHCCJCY10),
+        0x181D => Some(ServiceType::Scale1),
+        0x181B => Some(ServiceType::Scale2),
+        0x181A => Some(ServiceType::Atc),
         _ => None,
     }
 }
 
+/// Maps a raw BLE service UUID to a [ServiceType].
+///
+/// Accepts either a 16-bit UUID as 2 little-endian bytes (e.g. `[0x95, 0xFE]` for MiBeacon, as
+/// delivered by some BLE stacks) or a full 128-bit UUID as 16 bytes in big-endian order (as
+/// returned by [`Uuid::as_bytes`]). Any other length yields [`None`].
+#[must_use]
+pub fn service_type_from_bytes(bytes: &[u8]) -> Option<ServiceType> {
+    match bytes {
+        [low, high] => service_type_from_u16(u16::from_le_bytes([*low, *high])),
+        _ => Uuid::from_slice(bytes)
+            .ok()
+            .and_then(|uuid| service_uuid_to_type(&uuid)),
+    }
+}
+
+/// Maps a BLE manufacturer specific data company identifier to a [ServiceType].
+///
+/// Only Xiaomi's company identifier (0x038F, as assigned by the Bluetooth SIG) is handled. According
+/// to Xiaomi's MiBeacon protocol documentation, MiBeacon frames can be sent either as 0xFE95
+/// service data or as manufacturer specific data with this company identifier, using the same
+/// frame layout in both cases.
+///
+/// The ATC1441 and PVVX custom firmwares only advertise their own formats as 0x181A service data
+/// (see [`parse_service_advertisement`]), so they have no company identifier here.
+#[must_use]
+pub const fn company_id_to_type(company_id: u16) -> Option<ServiceType> {
+    const XIAOMI_COMPANY_ID: u16 = 0x038F;
+
+    match company_id {
+        XIAOMI_COMPANY_ID => Some(ServiceType::MiBeacon),
+        _ => None,
+    }
+}
+
+/// Parses a manufacturer specific data payload corresponding to the given company identifier.
+pub fn parse_manufacturer_data(
+    company_id: u16,
+    data: &[u8],
+) -> Result<ServiceAdvertisement, ServiceAdvertisementError> {
+    match company_id_to_type(company_id) {
+        Some(ServiceType::MiBeacon) => MiBeaconServiceAdvertisement::from_slice(data)
+            .map(ServiceAdvertisement::MiBeacon)
+            .map_err(ServiceAdvertisementError::ParsingFailed),
+        _ => Err(ServiceAdvertisementError::UnhandledManufacturer),
+    }
+}
+
 /// Parses a service advertisement payload corresponding to the given [UUID][Uuid].
 pub fn parse_service_advertisement(
     uuid: &Uuid,
     payload: &[u8],
 ) -> Result<ServiceAdvertisement, ServiceAdvertisementError> {
-    let service_type = service_uuid_to_type(uuid);
+    parse_service_advertisement_of_type(service_uuid_to_type(uuid), payload)
+}
+
+/// Parses a service advertisement payload corresponding to the given 16-bit service UUID (e.g.
+/// `0xFE95` for MiBeacon).
+pub fn parse_service_advertisement_u16(
+    service_id: u16,
+    payload: &[u8],
+) -> Result<ServiceAdvertisement, ServiceAdvertisementError> {
+    parse_service_advertisement_of_type(service_type_from_u16(service_id), payload)
+}
+
+/// Strips the header of a raw AD structure (e.g. from an HCI dump) that contains service data
+/// with a 16-bit UUID.
+///
+/// The AD structure consists of a length byte, the AD type (`0x16`), the 16-bit service UUID
+/// (little-endian) and the service data. Returns the full service [UUID][Uuid] and the service
+/// data (which can be passed to [`parse_service_advertisement`]), or [`None`] if the data is not
+/// a well-formed service data AD structure. Any bytes after the AD structure are ignored.
+#[must_use]
+pub fn strip_ad_header(data: &[u8]) -> Option<(Uuid, &[u8])> {
+    const AD_TYPE_SERVICE_DATA_16_BIT_UUID: u8 = 0x16;
+
+    let (&length, rest) = data.split_first()?;
+    let structure = rest.get(..usize::from(length))?;
+    match structure {
+        [AD_TYPE_SERVICE_DATA_16_BIT_UUID, uuid_low, uuid_high, payload @ ..] => {
+            let short_uuid = u16::from_le_bytes([*uuid_low, *uuid_high]);
+            let uuid = Uuid::from_u128(BLUETOOTH_BASE_UUID | (u128::from(short_uuid) << 96));
+            Some((uuid, payload))
+        }
+        _ => None,
+    }
+}
+
+/// Parses a service advertisement payload of the given [ServiceType].
+fn parse_service_advertisement_of_type(
+    service_type: Option<ServiceType>,
+    payload: &[u8],
+) -> Result<ServiceAdvertisement, ServiceAdvertisementError> {
     match service_type {
         Some(ServiceType::MiBeacon) => MiBeaconServiceAdvertisement::from_slice(payload)
             .map(ServiceAdvertisement::MiBeacon)
@@ -124,6 +395,597 @@ pub fn parse_service_advertisement(
         Some(ServiceType::HHCCJCY10) => HHCCJCY10ServiceAdvertisement::from_slice(payload)
             .map(ServiceAdvertisement::HHCCJCY10)
             .map_err(ServiceAdvertisementError::ParsingFailed),
+        Some(ServiceType::Atc) if payload.len() == PvvxServiceAdvertisement::LENGTH => {
+            PvvxServiceAdvertisement::from_slice(payload)
+                .map(ServiceAdvertisement::Pvvx)
+                .map_err(ServiceAdvertisementError::ParsingFailed)
+        }
+        Some(ServiceType::Atc) => AtcServiceAdvertisement::from_slice(payload)
+            .map(ServiceAdvertisement::Atc)
+            .map_err(ServiceAdvertisementError::ParsingFailed),
         _ => Err(ServiceAdvertisementError::UnhandledService),
     }
 }
+
+/// Parses a list of service data entries (as provided by any BLE stack) and returns each
+/// successfully parsed service advertisement together with its sensor events.
+///
+/// Entries with unhandled UUIDs or invalid payloads are skipped.
+#[must_use]
+pub fn parse_all_service_data(
+    entries: &[(Uuid, Vec<u8>)],
+) -> Vec<(ServiceAdvertisement, Vec<SensorEvent>)> {
+    entries
+        .iter()
+        .filter_map(|(uuid, payload)| parse_service_advertisement(uuid, payload).ok())
+        .map(|advertisement| {
+            let events = advertisement.iter_sensor_events().collect();
+            (advertisement, events)
+        })
+        .collect()
+}
+
+/// Parses the payload as each supported format that can be told apart by its content alone and
+/// returns the candidates that pass basic sanity checks.
+///
+/// These checks are heuristics to reject unrelated BLE data:
+///
+/// - MiBeacon: the frame control version is at most 5, the authentication mode is not reserved
+///   and the device ID is known.
+/// - HHCCJCY10: the payload has the exact length and moisture, battery power and temperature are
+///   in a plausible range.
+/// - Mi Scale: the device ID is known.
+fn plausible_candidates(payload: &[u8]) -> [Option<ServiceAdvertisement>; 3] {
+    [
+        MiBeaconServiceAdvertisement::from_slice(payload)
+            .ok()
+            .filter(|advertisement| {
+                advertisement.frame_control.version() <= 5
+                    && advertisement.frame_control.auth_mode() != AuthMode::Reserved
+                    && advertisement.device_type().is_some()
+            })
+            .map(ServiceAdvertisement::MiBeacon),
+        HHCCJCY10ServiceAdvertisement::from_slice(payload)
+            .ok()
+            .filter(|advertisement| {
+                payload.len() == HHCCJCY10ServiceAdvertisement::LENGTH
+                    && advertisement.moisture_percent <= 100
+                    && advertisement.battery_percent <= 100
+                    && advertisement.temperature_decicelsius <= 1000
+            })
+            .map(ServiceAdvertisement::HHCCJCY10),
+        MiScaleServiceAdvertisement::from_slice(payload)
+            .ok()
+            .filter(|advertisement| advertisement.device_type().is_some())
+            .map(ServiceAdvertisement::MiScale),
+    ]
+}
+
+/// Tries to parse a service advertisement payload without knowing its service [UUID][Uuid].
+///
+/// The payload is parsed as MiBeacon, HHCCJCY10 and Mi Scale advertisement (in that order) and
+/// the first one that passes basic sanity checks (see [`looks_like_xiaomi`]) and yields at least
+/// one sensor event is returned.
+#[must_use]
+pub fn try_parse_any(payload: &[u8]) -> Option<ServiceAdvertisement> {
+    plausible_candidates(payload)
+        .into_iter()
+        .flatten()
+        .find(|advertisement| advertisement.iter_sensor_events().next().is_some())
+}
+
+/// Returns `true` if the payload can be parsed as MiBeacon, HHCCJCY10 or Mi Scale advertisement
+/// and passes basic sanity checks (e.g. a plausible frame control header and a known device ID).
+///
+/// This is useful to filter arbitrary BLE data before passing it to [`try_parse_any`].
+#[must_use]
+pub fn looks_like_xiaomi(payload: &[u8]) -> bool {
+    plausible_candidates(payload).iter().any(Option::is_some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        looks_like_xiaomi, parse_all_service_data, parse_manufacturer_data,
+        parse_service_advertisement, parse_service_advertisement_u16, service_type_from_bytes,
+        service_type_from_u16, service_uuid_to_type, strip_ad_header, try_parse_any,
+        ServiceAdvertisement, ServiceAdvertisementError, ServiceType,
+    };
+    use crate::device::{DeviceRegistry, DeviceType};
+    use crate::sensor::{NumericMeasurementType, Reading, SensorEvent, UnitOfMeasurement};
+    use uuid::Uuid;
+
+    const MIBEACON_UUID: Uuid = Uuid::from_u128(0x0000fe95_0000_1000_8000_00805f9b34fb);
+    const ATC_UUID: Uuid = Uuid::from_u128(0x0000181a_0000_1000_8000_00805f9b34fb);
+
+    const HHCCJCY01_TEMPERATURE_READING: [u8; 17] = [
+        0x71, 0x20, 0x98, 0x00, 0xB1, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x04, 0x10, 0x02,
+        0xEC, 0x00,
+    ];
+    const HHCCJCY10_READING: [u8; 13] = [
+        0x0E, 0x00, 0x00, 0x00, 0x1A, 0xE5, 0x00, 0x10, 0x27, 0x00, 0x5A, 0xF4, 0x01,
+    ];
+    const ATC_READING: [u8; 13] = [
+        0xA4, 0xC1, 0x38, 0x2C, 0x6F, 0xA1, 0x00, 0xD9, 0x35, 0x5D, 0x0B, 0xA3, 0x2F,
+    ];
+    const PVVX_READING: [u8; 15] = [
+        0xA1, 0x6F, 0x2C, 0x38, 0xC1, 0xA4, 0x7D, 0x08, 0xC9, 0x14, 0xA3, 0x0B, 0x5D, 0x30, 0x05,
+    ];
+    const XMTZC01HM_READING: [u8; 14] = [
+        0x00, 0x00, 0x1D, 0x18, 0x20, 0xB0, 0x36, 0xE8, 0x07, 0x03, 0x18, 0x0A, 0x1B, 0x0C,
+    ];
+
+    #[test]
+    fn parse_xiaomi_manufacturer_data() {
+        // Manufacturer data uses the same MiBeacon frame layout as 0xFE95 service data, so the
+        // same frame must parse to the same advertisement.
+        let advertisement =
+            parse_manufacturer_data(0x038F, &HHCCJCY01_TEMPERATURE_READING).unwrap();
+        assert_eq!(
+            advertisement,
+            parse_service_advertisement(&MIBEACON_UUID, &HHCCJCY01_TEMPERATURE_READING).unwrap()
+        );
+        assert_eq!(advertisement.device_type().unwrap().model, "HHCCJCY01");
+        assert_eq!(advertisement.iter_sensor_events().count(), 1);
+    }
+
+    #[test]
+    fn advertisement_into_parts() {
+        let (advertisement, device_type) =
+            parse_service_advertisement(&MIBEACON_UUID, &HHCCJCY01_TEMPERATURE_READING)
+                .map(ServiceAdvertisement::into_parts)
+                .unwrap();
+        assert!(matches!(advertisement, ServiceAdvertisement::MiBeacon(_)));
+        assert_eq!(device_type.unwrap().model, "HHCCJCY01");
+
+        let mut reading = HHCCJCY01_TEMPERATURE_READING;
+        reading[2..4].copy_from_slice(&0xFFFFu16.to_le_bytes());
+        let (_, device_type) = parse_service_advertisement(&MIBEACON_UUID, &reading)
+            .unwrap()
+            .into_parts();
+        assert!(device_type.is_none());
+    }
+
+    #[test]
+    fn display_advertisement() {
+        let advertisement =
+            parse_service_advertisement(&MIBEACON_UUID, &HHCCJCY01_TEMPERATURE_READING).unwrap();
+        assert_eq!(
+            advertisement.to_string(),
+            "HHCCJCY01 (11:22:33:44:55:66)\ntemperature = 23.6 °C"
+        );
+    }
+
+    #[test]
+    fn embedded_mac_address() {
+        let advertisement =
+            parse_service_advertisement(&MIBEACON_UUID, &HHCCJCY01_TEMPERATURE_READING).unwrap();
+        assert_eq!(
+            advertisement.mac_address(),
+            Some([0x11, 0x22, 0x33, 0x44, 0x55, 0x66])
+        );
+
+        let advertisement = parse_service_advertisement_u16(0xFD50, &HHCCJCY10_READING).unwrap();
+        assert_eq!(advertisement.mac_address(), None);
+    }
+
+    #[test]
+    fn parse_service_advertisement_from_short_uuid() {
+        let advertisement =
+            parse_service_advertisement_u16(0xFE95, &HHCCJCY01_TEMPERATURE_READING).unwrap();
+        assert_eq!(
+            advertisement,
+            parse_service_advertisement(&MIBEACON_UUID, &HHCCJCY01_TEMPERATURE_READING).unwrap()
+        );
+        assert!(matches!(
+            parse_service_advertisement_u16(0xFEAF, &HHCCJCY01_TEMPERATURE_READING),
+            Err(ServiceAdvertisementError::UnhandledService)
+        ));
+    }
+
+    #[test]
+    fn strip_service_data_ad_header() {
+        let mut structure = vec![0x14, 0x16, 0x95, 0xFE];
+        structure.extend_from_slice(&HHCCJCY01_TEMPERATURE_READING);
+        let (uuid, payload) = strip_ad_header(&structure).unwrap();
+        assert_eq!(uuid, MIBEACON_UUID);
+        assert_eq!(payload, HHCCJCY01_TEMPERATURE_READING);
+        assert!(parse_service_advertisement(&uuid, payload).is_ok());
+
+        // Trailing AD structures are ignored.
+        structure.extend_from_slice(&[0x02, 0x01, 0x06]);
+        assert_eq!(
+            strip_ad_header(&structure),
+            Some((MIBEACON_UUID, &HHCCJCY01_TEMPERATURE_READING[..]))
+        );
+
+        // Truncated AD structure
+        assert_eq!(strip_ad_header(&structure[..10]), None);
+        // Manufacturer specific data (AD type 0xFF)
+        assert_eq!(strip_ad_header(&[0x05, 0xFF, 0x8F, 0x03, 0x00, 0x00]), None);
+        // Too short for the 16-bit UUID
+        assert_eq!(strip_ad_header(&[0x02, 0x16, 0x95]), None);
+        assert_eq!(strip_ad_header(&[]), None);
+    }
+
+    #[test]
+    fn parse_multiple_service_data_entries() {
+        const UNKNOWN_UUID: Uuid = Uuid::from_u128(0x0000feaf_0000_1000_8000_00805f9b34fb);
+        let entries = [
+            (MIBEACON_UUID, HHCCJCY01_TEMPERATURE_READING.to_vec()),
+            (UNKNOWN_UUID, vec![0x01, 0x02, 0x03]),
+            (
+                ATC_UUID,
+                vec![
+                    0xA4, 0xC1, 0x38, 0x2C, 0x6F, 0xA1, 0x00, 0xD9, 0x35, 0x5D, 0x0B, 0xA3, 0x2F,
+                ],
+            ),
+        ];
+
+        let parsed = parse_all_service_data(&entries);
+        assert_eq!(parsed.len(), 2);
+        assert!(matches!(parsed[0].0, ServiceAdvertisement::MiBeacon(_)));
+        assert_eq!(parsed[0].1.len(), 1);
+        assert!(matches!(parsed[1].0, ServiceAdvertisement::Atc(_)));
+        assert_eq!(parsed[1].1.len(), 4);
+    }
+
+    #[test]
+    fn readings_carry_source_object_id() {
+        let advertisement =
+            parse_service_advertisement(&MIBEACON_UUID, &HHCCJCY01_TEMPERATURE_READING).unwrap();
+        let readings: Vec<Reading> = advertisement.iter_readings().collect();
+        assert_eq!(readings.len(), 1);
+        assert_eq!(readings[0].source_object_id, Some(0x1004));
+        assert!(matches!(
+            readings[0].event,
+            SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Temperature,
+                ..
+            }
+        ));
+
+        let advertisement = try_parse_any(&HHCCJCY10_READING).unwrap();
+        assert_eq!(advertisement.iter_readings().count(), 4);
+        assert!(advertisement
+            .iter_readings()
+            .all(|reading| reading.source_object_id.is_none()));
+    }
+
+    #[test]
+    fn try_parse_any_classifies_payloads() {
+        assert!(matches!(
+            try_parse_any(&HHCCJCY01_TEMPERATURE_READING),
+            Some(ServiceAdvertisement::MiBeacon(_))
+        ));
+        assert!(matches!(
+            try_parse_any(&HHCCJCY10_READING),
+            Some(ServiceAdvertisement::HHCCJCY10(_))
+        ));
+        assert!(matches!(
+            try_parse_any(&XMTZC01HM_READING),
+            Some(ServiceAdvertisement::MiScale(_))
+        ));
+    }
+
+    #[test]
+    fn weight_is_reported_in_kilograms() {
+        const MIBEACON_WEIGHT_READING: [u8; 17] = [
+            0x71, 0x20, 0x00, 0x00, 0x39, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x1A, 0x10,
+            0x02, 0xB8, 0x0B,
+        ];
+
+        // The MiBeacon weight object (0x101A) is specified in grams: 3000 g must not be reported
+        // as 3000 kg.
+        let advertisement =
+            parse_service_advertisement(&MIBEACON_UUID, &MIBEACON_WEIGHT_READING).unwrap();
+        let events: Vec<SensorEvent> = advertisement.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Weight,
+                value: 3.0,
+                unit: UnitOfMeasurement::Kilogram,
+            }]
+        );
+
+        let advertisement = try_parse_any(&XMTZC01HM_READING).unwrap();
+        let events: Vec<SensorEvent> = advertisement.iter_sensor_events().collect();
+        assert_eq!(
+            events,
+            vec![SensorEvent::NumericMeasurement {
+                measurement_type: NumericMeasurementType::Weight,
+                value: 70.0,
+                unit: UnitOfMeasurement::Kilogram,
+            }]
+        );
+    }
+
+    #[test]
+    fn service_type_from_short_and_full_uuid() {
+        assert_eq!(service_type_from_u16(0xFE95), Some(ServiceType::MiBeacon));
+        assert_eq!(service_type_from_u16(0x181D), Some(ServiceType::Scale1));
+        assert_eq!(service_type_from_u16(0xFEAF), None);
+
+        assert_eq!(
+            service_uuid_to_type(&MIBEACON_UUID),
+            Some(ServiceType::MiBeacon)
+        );
+        assert_eq!(
+            service_uuid_to_type(&Uuid::from_u128(0x0000181d_0000_1000_8000_00805f9b34fb)),
+            Some(ServiceType::Scale1)
+        );
+        // Same 16-bit value, but not derived from the Bluetooth Base UUID.
+        assert_eq!(
+            service_uuid_to_type(&Uuid::from_u128(0x0000fe95_0000_1000_8000_000000000000)),
+            None
+        );
+        assert_eq!(
+            service_uuid_to_type(&Uuid::from_u128(0x1234fe95_0000_1000_8000_00805f9b34fb)),
+            None
+        );
+    }
+
+    #[test]
+    fn service_type_from_raw_bytes() {
+        assert_eq!(
+            service_type_from_bytes(&[0x95, 0xFE]),
+            Some(ServiceType::MiBeacon)
+        );
+        assert_eq!(
+            service_type_from_bytes(MIBEACON_UUID.as_bytes()),
+            Some(ServiceType::MiBeacon)
+        );
+        assert_eq!(service_type_from_bytes(&[0xFE, 0x95]), None);
+        assert_eq!(service_type_from_bytes(&[0x95, 0xFE, 0x00]), None);
+        assert_eq!(service_type_from_bytes(&[]), None);
+    }
+
+    #[test]
+    fn try_parse_any_rejects_noise() {
+        const NOISE: [&[u8]; 4] = [
+            &[],
+            &[
+                0x3B, 0xA7, 0x19, 0xF2, 0x8C, 0x55, 0xE1, 0x07, 0xC4, 0x9D, 0x62, 0x2F,
+            ],
+            &[
+                0x3B, 0xA7, 0x19, 0xF2, 0xC8, 0x55, 0xE1, 0x07, 0xC4, 0x9D, 0x62, 0x2F, 0x80,
+            ],
+            &[
+                0xD2, 0x4E, 0x91, 0x0C, 0x7A, 0x33, 0xB8, 0x6F, 0x15, 0xE9, 0x40, 0xAB, 0x27, 0x5C,
+                0x88, 0x1D, 0xF6,
+            ],
+        ];
+
+        for payload in NOISE {
+            assert!(try_parse_any(payload).is_none(), "{payload:02X?}");
+        }
+    }
+
+    #[test]
+    fn looks_like_xiaomi_rejects_random_noise() {
+        // Simple linear congruential generator, so that the test is deterministic.
+        let mut state: u32 = 0x1234_5678;
+        let mut next_byte = move || {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 24) as u8
+        };
+
+        for length in 0..40 {
+            for _ in 0..50 {
+                let payload: Vec<u8> = (0..length).map(|_| next_byte()).collect();
+                assert!(!looks_like_xiaomi(&payload), "{payload:02X?}");
+            }
+        }
+
+        assert!(looks_like_xiaomi(&HHCCJCY01_TEMPERATURE_READING));
+        assert!(looks_like_xiaomi(&HHCCJCY10_READING));
+        let mut unknown_version = HHCCJCY01_TEMPERATURE_READING;
+        unknown_version[1] = 0x60;
+        assert!(!looks_like_xiaomi(&unknown_version));
+    }
+
+    #[test]
+    fn clone_parsed_advertisement() {
+        let advertisement =
+            parse_service_advertisement(&MIBEACON_UUID, &HHCCJCY01_TEMPERATURE_READING).unwrap();
+        let cloned = advertisement.clone();
+        assert_eq!(cloned, advertisement);
+        if let ServiceAdvertisement::MiBeacon(mibeacon) = &advertisement {
+            assert_eq!(mibeacon.clone().objects, mibeacon.objects);
+        } else {
+            panic!("expected a MiBeacon advertisement");
+        }
+    }
+
+    #[test]
+    fn parse_custom_firmware_by_length() {
+        assert!(matches!(
+            parse_service_advertisement(&ATC_UUID, &ATC_READING),
+            Ok(ServiceAdvertisement::Atc(_))
+        ));
+        assert!(matches!(
+            parse_service_advertisement(&ATC_UUID, &PVVX_READING),
+            Ok(ServiceAdvertisement::Pvvx(_))
+        ));
+    }
+
+    #[test]
+    fn mac_address_in_display_order() {
+        let advertisement =
+            parse_service_advertisement(&MIBEACON_UUID, &HHCCJCY01_TEMPERATURE_READING).unwrap();
+        assert_eq!(
+            advertisement.mac_address(),
+            Some([0x11, 0x22, 0x33, 0x44, 0x55, 0x66])
+        );
+        for reading in [&ATC_READING[..], &PVVX_READING[..]] {
+            let advertisement = parse_service_advertisement(&ATC_UUID, reading).unwrap();
+            assert_eq!(
+                advertisement.mac_address(),
+                Some([0xA4, 0xC1, 0x38, 0x2C, 0x6F, 0xA1])
+            );
+        }
+        let advertisement = parse_service_advertisement_u16(0xFD50, &HHCCJCY10_READING).unwrap();
+        assert_eq!(advertisement.mac_address(), None);
+    }
+
+    #[test]
+    fn custom_firmware_mac_addresses_match() {
+        // Both fixtures were sent by the same device, so the MAC addresses must be equal although
+        // the PVVX format transmits it in reverse order.
+        let Ok(ServiceAdvertisement::Atc(atc)) =
+            parse_service_advertisement(&ATC_UUID, &ATC_READING)
+        else {
+            panic!("expected an ATC advertisement");
+        };
+        let Ok(ServiceAdvertisement::Pvvx(pvvx)) =
+            parse_service_advertisement(&ATC_UUID, &PVVX_READING)
+        else {
+            panic!("expected a PVVX advertisement");
+        };
+        assert_eq!(atc.mac_address, pvvx.mac_address);
+    }
+
+    #[test]
+    fn expected_measurements_match_events() {
+        for advertisement in [
+            parse_service_advertisement(&ATC_UUID, &ATC_READING).unwrap(),
+            parse_service_advertisement(&ATC_UUID, &PVVX_READING).unwrap(),
+            parse_service_advertisement_u16(0xFD50, &HHCCJCY10_READING).unwrap(),
+        ] {
+            let kinds: Vec<_> = advertisement
+                .iter_sensor_events()
+                .filter_map(|event| event.measurement_kind())
+                .collect();
+            assert_eq!(
+                kinds,
+                advertisement.device_type().unwrap().expected_measurements()
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn timestamped_sensor_events() {
+        use std::time::{Duration, SystemTime};
+
+        let received_at = SystemTime::UNIX_EPOCH + Duration::from_secs(0x6600_0000);
+        let advertisement = parse_service_advertisement_u16(0xFD50, &HHCCJCY10_READING).unwrap();
+        let events: Vec<_> = advertisement.iter_sensor_events_at(received_at).collect();
+        assert_eq!(events.len(), 4);
+        assert!(events.iter().all(|event| event.received_at == received_at));
+        assert_eq!(
+            events.iter().map(|event| event.event).collect::<Vec<_>>(),
+            advertisement.iter_sensor_events().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn latest_events() {
+        let reading = [
+            0x71, 0x20, 0xDF, 0x02, 0x01, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x0D, 0x04, 0x10,
+            0x02, 0xEC, 0x00, 0x10, 0x10, 0x02, 0x0A, 0x00, 0x1C, 0x10, 0x02, 0x70, 0x00,
+        ];
+        let advertisement = parse_service_advertisement(&MIBEACON_UUID, &reading).unwrap();
+        assert_eq!(advertisement.iter_sensor_events().count(), 3);
+        assert_eq!(
+            advertisement.latest_events(),
+            [
+                SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::Temperature,
+                    value: 23.6,
+                    unit: UnitOfMeasurement::DegreesCelsius,
+                },
+                SensorEvent::NumericMeasurement {
+                    measurement_type: NumericMeasurementType::FormaldehydeConcentration,
+                    value: 0.112,
+                    unit: UnitOfMeasurement::MilligramPerCubicMeter,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn sequence_number() {
+        let advertisement =
+            parse_service_advertisement(&MIBEACON_UUID, &HHCCJCY01_TEMPERATURE_READING).unwrap();
+        assert_eq!(advertisement.sequence_number(), Some(0xB1));
+        let advertisement = parse_service_advertisement(&ATC_UUID, &ATC_READING).unwrap();
+        assert_eq!(advertisement.sequence_number(), Some(0x2F));
+        let advertisement = parse_service_advertisement(&ATC_UUID, &PVVX_READING).unwrap();
+        assert_eq!(advertisement.sequence_number(), Some(0x30));
+        let advertisement = parse_service_advertisement_u16(0xFD50, &HHCCJCY10_READING).unwrap();
+        assert_eq!(advertisement.sequence_number(), None);
+        let advertisement = try_parse_any(&XMTZC01HM_READING).unwrap();
+        assert_eq!(advertisement.sequence_number(), None);
+    }
+
+    #[test]
+    fn device_type_with_registry() {
+        let mut registry = DeviceRegistry::new();
+        let mut reading = HHCCJCY01_TEMPERATURE_READING;
+        reading[2..4].copy_from_slice(&0xFFFFu16.to_le_bytes());
+        let advertisement = parse_service_advertisement(&MIBEACON_UUID, &reading).unwrap();
+        assert!(advertisement.device_type().is_none());
+        assert!(advertisement.device_type_with_registry(&registry).is_none());
+
+        registry.register(
+            0xFFFF,
+            DeviceType {
+                name: "Custom Sensor".into(),
+                model: "CUSTOM".into(),
+                manufacturer: "Custom".into(),
+            },
+        );
+        assert_eq!(
+            advertisement
+                .device_type_with_registry(&registry)
+                .unwrap()
+                .model,
+            "CUSTOM"
+        );
+
+        let advertisement = parse_service_advertisement(&ATC_UUID, &ATC_READING).unwrap();
+        assert_eq!(
+            advertisement
+                .device_type_with_registry(&registry)
+                .unwrap()
+                .model,
+            "ATC"
+        );
+    }
+
+    #[test]
+    fn parse_unknown_manufacturer_data() {
+        assert!(matches!(
+            parse_manufacturer_data(0x004C, &HHCCJCY01_TEMPERATURE_READING),
+            Err(ServiceAdvertisementError::UnhandledManufacturer)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_hhccjcy01_temperature_summary() {
+        let advertisement =
+            parse_service_advertisement(&MIBEACON_UUID, &HHCCJCY01_TEMPERATURE_READING).unwrap();
+        assert_eq!(
+            serde_json::to_value(advertisement.summary()).unwrap(),
+            serde_json::json!({
+                "device_type": {
+                    "name": "Plant Sensor",
+                    "model": "HHCCJCY01",
+                    "manufacturer": "Xiaomi",
+                },
+                "mac_address": "11:22:33:44:55:66",
+                "events": [
+                    {
+                        "kind": "numeric_measurement",
+                        "measurement_type": "temperature",
+                        "value": 23.6,
+                        "unit": "°C",
+                    },
+                ],
+            })
+        );
+    }
+}
@@ -8,28 +8,188 @@
 
 //! Utilities in multiple modules within this crate.
 
-use binrw::BinRead;
+use binrw::{BinRead, BinWrite};
 use core::fmt;
 use thiserror::Error;
 
 /// Raised when error occurs while parsing a data structure.
 #[derive(Debug, Error)]
-#[error("Parsing failed: {source}")]
-pub struct ParseError {
-    // The underlying `binrw` error.
-    #[from]
-    source: binrw::Error,
+pub enum ParseError {
+    /// The payload ended before the data structure was read completely (i.e. the packet was cut
+    /// short).
+    #[error("Parsing failed: Payload truncated ({found} bytes)")]
+    Truncated {
+        /// The number of bytes the data structure requires (if known).
+        expected: Option<usize>,
+        /// The number of bytes in the payload.
+        found: usize,
+    },
+    /// A MiBeacon object declares a length that exceeds the remaining bytes of the payload.
+    #[error(
+        "Parsing failed: Object 0x{object_id:04X} declares {length} bytes, but only {remaining} bytes remain"
+    )]
+    ObjectLengthExceedsBuffer {
+        /// The ID of the object.
+        object_id: u16,
+        /// The declared length of the object.
+        length: u8,
+        /// The number of remaining bytes in the payload.
+        remaining: usize,
+    },
+    /// The device ID in the payload does not belong to a known device of the protocol.
+    #[error("Parsing failed: Unknown device ID 0x{device_id:04X}")]
+    UnknownDeviceId {
+        /// The device ID.
+        device_id: u16,
+    },
+    /// The payload contains more MiBeacon objects than allowed (see
+    /// [`MiBeaconServiceAdvertisement::MAX_OBJECTS`](crate::mibeacon::MiBeaconServiceAdvertisement::MAX_OBJECTS)).
+    #[error("Parsing failed: Payload contains more than {limit} objects")]
+    TooManyObjects {
+        /// The maximum number of objects.
+        limit: usize,
+    },
+    /// The payload is a MiBeacon mesh advertisement (see
+    /// [`FrameControl::mesh()`](crate::mibeacon::FrameControl::mesh)), which is not supported.
+    #[error("Parsing failed: MiBeacon mesh advertisements are not supported")]
+    MeshUnsupported,
+    /// The payload contains an invalid value.
+    #[error("Parsing failed: {inner}")]
+    Invalid {
+        /// The underlying `binrw` error (only implements `Error` if the `std` feature is enabled).
+        #[cfg_attr(feature = "std", source)]
+        inner: binrw::Error,
+    },
 }
 
+impl ParseError {
+    /// Creates a [`ParseError`] from a `binrw` error that occurred while reading a payload of
+    /// `found` bytes (of `expected` bytes, if known).
+    pub(crate) fn from_binrw(inner: binrw::Error, found: usize, expected: Option<usize>) -> Self {
+        if let Some(&ObjectLengthError {
+            object_id,
+            length,
+            remaining,
+        }) = inner.custom_err::<ObjectLengthError>()
+        {
+            Self::ObjectLengthExceedsBuffer {
+                object_id,
+                length,
+                remaining,
+            }
+        } else if let Some(&TooManyObjectsError { limit }) =
+            inner.custom_err::<TooManyObjectsError>()
+        {
+            Self::TooManyObjects { limit }
+        } else if inner.custom_err::<MeshUnsupportedError>().is_some() {
+            Self::MeshUnsupported
+        } else if is_truncation(&inner) {
+            Self::Truncated { expected, found }
+        } else {
+            Self::Invalid { inner }
+        }
+    }
+}
+
+/// Custom `binrw` error raised if a MiBeacon object declares a length that exceeds the remaining
+/// bytes of the payload (converted to [`ParseError::ObjectLengthExceedsBuffer`]).
+#[derive(Debug, Error)]
+#[error("Object 0x{object_id:04X} declares {length} bytes, but only {remaining} bytes remain")]
+pub(crate) struct ObjectLengthError {
+    pub(crate) object_id: u16,
+    pub(crate) length: u8,
+    pub(crate) remaining: usize,
+}
+
+/// Custom `binrw` error raised if a MiBeacon advertisement contains too many objects (converted to
+/// [`ParseError::TooManyObjects`]).
+#[derive(Debug, Error)]
+#[error("Payload contains more than {limit} objects")]
+pub(crate) struct TooManyObjectsError {
+    pub(crate) limit: usize,
+}
+
+/// Custom `binrw` error raised if a MiBeacon advertisement has the mesh flag set (converted to
+/// [`ParseError::MeshUnsupported`]).
+#[derive(Debug, Error)]
+#[error("MiBeacon mesh advertisements are not supported")]
+pub(crate) struct MeshUnsupportedError;
+
+/// Returns `true` if the error was caused by an unexpected end of the payload.
+///
+/// Unlike [`binrw::Error::is_eof`], this ignores enum variants that were skipped due to a failed
+/// assertion (e.g. a non-matching object ID), so that truncated MiBeacon objects are detected.
+fn is_truncation(error: &binrw::Error) -> bool {
+    match error {
+        binrw::Error::Backtrace(backtrace) => is_truncation(&backtrace.error),
+        binrw::Error::EnumErrors { variant_errors, .. } => {
+            let mut errors = variant_errors
+                .iter()
+                .map(|(_, error)| error)
+                .filter(|error| !is_assertion_failure(error))
+                .peekable();
+            errors.peek().is_some() && errors.all(is_truncation)
+        }
+        error => error.is_eof(),
+    }
+}
+
+/// Returns `true` if the error was caused by a failed assertion.
+fn is_assertion_failure(error: &binrw::Error) -> bool {
+    match error {
+        binrw::Error::Backtrace(backtrace) => is_assertion_failure(&backtrace.error),
+        binrw::Error::AssertFail { .. } => true,
+        _ => false,
+    }
+}
+
+/// Logs a warning via the `log` crate (if the `logging` feature is enabled).
+macro_rules! log_warn {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "logging")]
+        log::warn!($($arg)+);
+        #[cfg(not(feature = "logging"))]
+        let _ = format_args!($($arg)+);
+    }};
+}
+pub(crate) use log_warn;
+
 /// Unsigned integer consisting of 3 bytes.
-#[derive(BinRead)]
-#[br(little)]
+#[derive(BinRead, BinWrite, Copy, Clone, PartialEq, Eq)]
+#[brw(little)]
 pub struct U24([u8; 3]);
 
 impl U24 {
+    /// The largest value that can be represented by this type.
+    pub const MAX: u32 = 0x00FF_FFFF;
+
     /// Get the number as [`u32`].
-    pub fn as_u32(&self) -> u32 {
-        u32::from(self.0[2]) << 16 | u32::from(self.0[1]) << 8 | u32::from(self.0[0])
+    #[must_use]
+    pub const fn as_u32(&self) -> u32 {
+        (self.0[2] as u32) << 16 | (self.0[1] as u32) << 8 | self.0[0] as u32
+    }
+
+    /// Create a number from a [`u32`] (returns [`None`] if the value exceeds [`U24::MAX`]).
+    #[must_use]
+    pub const fn from_u32(value: u32) -> Option<Self> {
+        if value > Self::MAX {
+            return None;
+        }
+        let [b0, b1, b2, _] = value.to_le_bytes();
+        Some(Self([b0, b1, b2]))
+    }
+}
+
+/// Raised when a value is too large to be converted into a [`U24`].
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[error("Value exceeds the range of a 24-bit unsigned integer")]
+pub struct U24OutOfRangeError;
+
+impl TryFrom<u32> for U24 {
+    type Error = U24OutOfRangeError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Self::from_u32(value).ok_or(U24OutOfRangeError)
     }
 }
 
@@ -38,3 +198,18 @@ impl fmt::Debug for U24 {
         write!(f, "{}", self.as_u32())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{U24OutOfRangeError, U24};
+
+    #[test]
+    fn u24_from_u32_bounds() {
+        assert_eq!(U24::from_u32(0).unwrap().as_u32(), 0);
+        assert_eq!(U24::from_u32(0x12_3456).unwrap().as_u32(), 0x12_3456);
+        assert_eq!(U24::from_u32(0xFF_FFFF).unwrap().as_u32(), 0xFF_FFFF);
+        assert!(U24::from_u32(0x100_0000).is_none());
+        assert_eq!(U24::try_from(0xFF_FFFF).unwrap().as_u32(), 0xFF_FFFF);
+        assert_eq!(U24::try_from(0x100_0000), Err(U24OutOfRangeError));
+    }
+}